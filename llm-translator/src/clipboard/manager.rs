@@ -1,9 +1,21 @@
-use arboard::Clipboard;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 use thiserror::Error;
+use tokio::sync::RwLock;
 use tokio::time::sleep;
 use tracing::{debug, warn, error};
 
+use super::provider::{build_provider, ClipboardProvider, ClipboardType};
+use crate::config::ClipboardSettings;
+
+fn hash_text(text: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    text.hash(&mut hasher);
+    hasher.finish()
+}
+
 #[derive(Error, Debug)]
 pub enum ClipboardError {
     #[error("Failed to access clipboard: {0}")]
@@ -38,32 +50,54 @@ pub enum SelectionError {
 }
 
 pub struct ClipboardManager {
-    clipboard: Clipboard,
+    provider: Box<dyn ClipboardProvider>,
     original_content: Option<String>,
     preserve_original: bool,
     timeout_ms: u64,
 }
 
 impl ClipboardManager {
+    /// Back-compat constructor: auto-detects a provider instead of taking
+    /// `Config::clipboard` into account. Prefer `with_settings`.
     pub fn new(preserve_original: bool, timeout_ms: u64) -> Result<Self, ClipboardError> {
-        let clipboard = Clipboard::new()
-            .map_err(|e| ClipboardError::AccessError(e.to_string()))?;
-        
+        Self::with_settings(preserve_original, timeout_ms, &ClipboardSettings::default())
+    }
+
+    pub fn with_settings(
+        preserve_original: bool,
+        timeout_ms: u64,
+        clipboard: &ClipboardSettings,
+    ) -> Result<Self, ClipboardError> {
+        let provider = build_provider(clipboard, timeout_ms)?;
+
         Ok(Self {
-            clipboard,
+            provider,
             original_content: None,
             preserve_original,
             timeout_ms,
         })
     }
 
-    /// Get currently selected text by simulating Ctrl+C
+    /// Get currently selected text. On Linux/X11/Wayland, a highlighted
+    /// selection already lives in the PRIMARY clipboard, so if the detected
+    /// provider genuinely supports it we read it directly and skip the
+    /// intrusive, racy synthetic Ctrl+C below entirely.
     pub async fn get_selection(&mut self) -> Result<String, SelectionError> {
         debug!("Getting text selection");
-        
+
+        if self.provider.supports_primary() {
+            if let Ok(text) = self.get_text(ClipboardType::Primary) {
+                let trimmed = text.trim();
+                if !trimmed.is_empty() {
+                    debug!("Captured selection directly from PRIMARY: {} chars", text.len());
+                    return Ok(text);
+                }
+            }
+        }
+
         // Save original clipboard content if needed
         if self.preserve_original {
-            self.original_content = self.get_text().ok();
+            self.original_content = self.get_text(ClipboardType::Clipboard).ok();
             debug!("Saved original clipboard content");
         }
 
@@ -77,10 +111,10 @@ impl ClipboardManager {
         // Wait for clipboard to be populated with timeout
         let deadline = Instant::now() + Duration::from_millis(self.timeout_ms);
         let mut check_interval = Duration::from_millis(10);
-        
+
         loop {
             // Check if clipboard has new content
-            if let Ok(text) = self.get_text() {
+            if let Ok(text) = self.get_text(ClipboardType::Clipboard) {
                 if !text.is_empty() {
                     // Validate the text
                     let trimmed = text.trim();
@@ -112,34 +146,61 @@ impl ClipboardManager {
         }
     }
 
-    /// Set clipboard text
-    pub fn set_text(&mut self, text: &str) -> Result<(), ClipboardError> {
-        self.clipboard
-            .set_text(text)
-            .map_err(|e| ClipboardError::AccessError(e.to_string()))?;
-        
-        debug!("Set clipboard text: {} chars", text.len());
+    /// Set text on the given clipboard channel
+    pub fn set_text(&mut self, selection: ClipboardType, text: &str) -> Result<(), ClipboardError> {
+        self.provider.set_contents(selection, text)?;
+
+        debug!("Set clipboard text via {} provider: {} chars", self.provider.name(), text.len());
         Ok(())
     }
 
-    /// Get clipboard text
-    pub fn get_text(&mut self) -> Result<String, ClipboardError> {
-        self.clipboard
-            .get_text()
-            .map_err(|e| ClipboardError::AccessError(e.to_string()))
+    /// Get text from the given clipboard channel
+    pub fn get_text(&mut self, selection: ClipboardType) -> Result<String, ClipboardError> {
+        self.provider.get_contents(selection)
     }
 
     /// Clear clipboard
     pub fn clear(&mut self) -> Result<(), ClipboardError> {
-        self.set_text("")
+        self.set_text(ClipboardType::Clipboard, "")
+    }
+
+    /// Write `text` to the clipboard, then schedule a background wipe after
+    /// `delay` so sensitive text (passwords, private messages) doesn't
+    /// linger in the system clipboard indefinitely. Before wiping, the
+    /// clipboard is re-read and compared by hash against what we wrote, so
+    /// if the user copied something else in the meantime the wipe is skipped.
+    pub async fn set_text_ephemeral(
+        manager: &Arc<RwLock<Self>>,
+        text: &str,
+        delay: Duration,
+    ) -> Result<(), ClipboardError> {
+        let written_hash = {
+            let mut guard = manager.write().await;
+            guard.set_text(ClipboardType::Clipboard, text)?;
+            hash_text(text)
+        };
+
+        let manager = Arc::clone(manager);
+        tokio::spawn(async move {
+            sleep(delay).await;
+            let mut guard = manager.write().await;
+            if let Ok(current) = guard.get_text(ClipboardType::Clipboard) {
+                if hash_text(&current) == written_hash {
+                    debug!("Auto-clearing clipboard after ephemeral write");
+                    let _ = guard.clear();
+                }
+            }
+        });
+
+        Ok(())
     }
 
     /// Restore original clipboard content
     pub async fn restore_original(&mut self) -> Result<(), ClipboardError> {
         if let Some(ref original) = self.original_content {
             debug!("Restoring original clipboard content");
-            self.set_text(original)?;
-            
+            self.set_text(ClipboardType::Clipboard, original)?;
+
             // Small delay to ensure clipboard is updated
             sleep(Duration::from_millis(50)).await;
         }
@@ -277,9 +338,9 @@ impl ClipboardManager {
     /// Replace selected text with new content
     pub async fn replace_selection(&mut self, new_text: &str) -> Result<(), SelectionError> {
         debug!("Replacing selection with {} chars", new_text.len());
-        
+
         // Set new text to clipboard
-        self.set_text(new_text)
+        self.set_text(ClipboardType::Clipboard, new_text)
             .map_err(|e| SelectionError::ClipboardError(e.to_string()))?;
         
         // Simulate paste
@@ -310,11 +371,11 @@ mod tests {
     #[tokio::test]
     async fn test_set_and_get_text() {
         let mut manager = ClipboardManager::new(false, 500).unwrap();
-        
+
         let test_text = "Hello, clipboard!";
-        assert!(manager.set_text(test_text).is_ok());
-        
-        let retrieved = manager.get_text();
+        assert!(manager.set_text(ClipboardType::Clipboard, test_text).is_ok());
+
+        let retrieved = manager.get_text(ClipboardType::Clipboard);
         assert!(retrieved.is_ok());
         assert_eq!(retrieved.unwrap(), test_text);
     }
@@ -322,41 +383,107 @@ mod tests {
     #[tokio::test]
     async fn test_clear_clipboard() {
         let mut manager = ClipboardManager::new(false, 500).unwrap();
-        
-        manager.set_text("Some text").unwrap();
+
+        manager.set_text(ClipboardType::Clipboard, "Some text").unwrap();
         assert!(manager.clear().is_ok());
-        
-        let text = manager.get_text().unwrap();
+
+        let text = manager.get_text(ClipboardType::Clipboard).unwrap();
         assert!(text.is_empty());
     }
 
     #[tokio::test]
     async fn test_preserve_original() {
         let mut manager = ClipboardManager::new(true, 500).unwrap();
-        
+
         let original = "Original text";
-        manager.set_text(original).unwrap();
-        
+        manager.set_text(ClipboardType::Clipboard, original).unwrap();
+
         // Simulate saving original
         manager.original_content = Some(original.to_string());
-        
+
         // Change clipboard
-        manager.set_text("New text").unwrap();
-        
+        manager.set_text(ClipboardType::Clipboard, "New text").unwrap();
+
         // Restore
         manager.restore_original().await.unwrap();
-        
-        let restored = manager.get_text().unwrap();
+
+        let restored = manager.get_text(ClipboardType::Clipboard).unwrap();
         assert_eq!(restored, original);
     }
 
+    #[tokio::test]
+    async fn test_primary_and_clipboard_are_independent_channels() {
+        let mut manager = ClipboardManager::new(false, 500).unwrap();
+
+        manager.set_text(ClipboardType::Clipboard, "clipboard text").unwrap();
+        manager.set_text(ClipboardType::Primary, "primary text").unwrap();
+
+        assert_eq!(manager.get_text(ClipboardType::Clipboard).unwrap(), "clipboard text");
+        assert_eq!(manager.get_text(ClipboardType::Primary).unwrap(), "primary text");
+    }
+
+    #[tokio::test]
+    async fn test_set_text_ephemeral_clears_after_delay() {
+        let manager = Arc::new(RwLock::new(ClipboardManager::new(false, 500).unwrap()));
+
+        ClipboardManager::set_text_ephemeral(&manager, "super secret", Duration::from_millis(20))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            manager.write().await.get_text(ClipboardType::Clipboard).unwrap(),
+            "super secret"
+        );
+
+        sleep(Duration::from_millis(80)).await;
+
+        assert_eq!(manager.write().await.get_text(ClipboardType::Clipboard).unwrap(), "");
+    }
+
+    #[tokio::test]
+    async fn test_set_text_ephemeral_skips_clear_if_clipboard_changed() {
+        let manager = Arc::new(RwLock::new(ClipboardManager::new(false, 500).unwrap()));
+
+        ClipboardManager::set_text_ephemeral(&manager, "super secret", Duration::from_millis(20))
+            .await
+            .unwrap();
+
+        manager.write().await.set_text(ClipboardType::Clipboard, "user copied this instead").unwrap();
+
+        sleep(Duration::from_millis(80)).await;
+
+        assert_eq!(
+            manager.write().await.get_text(ClipboardType::Clipboard).unwrap(),
+            "user copied this instead"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_with_settings_uses_configured_custom_provider() {
+        use crate::config::{ClipboardCommandSpec, ClipboardProviderKind, CustomClipboardCommand};
+
+        let settings = ClipboardSettings {
+            provider: ClipboardProviderKind::Custom,
+            custom: Some(CustomClipboardCommand {
+                yank: ClipboardCommandSpec { command: "cat".to_string(), args: vec![] },
+                paste: ClipboardCommandSpec {
+                    command: "echo".to_string(),
+                    args: vec!["from custom provider".to_string()],
+                },
+            }),
+        };
+
+        let mut manager = ClipboardManager::with_settings(false, 500, &settings).unwrap();
+        assert_eq!(manager.get_text(ClipboardType::Clipboard).unwrap().trim(), "from custom provider");
+    }
+
     #[tokio::test]
     async fn test_empty_selection_detection() {
         let mut manager = ClipboardManager::new(false, 100).unwrap();
-        
+
         // Clear clipboard to simulate no selection
         manager.clear().unwrap();
-        
+
         // This should timeout since we're not actually simulating copy
         let result = manager.get_selection().await;
         assert!(result.is_err());
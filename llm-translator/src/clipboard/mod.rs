@@ -0,0 +1,8 @@
+pub mod manager;
+pub mod provider;
+
+pub use manager::{ClipboardError, ClipboardManager, SelectionError};
+pub use provider::{
+    build_provider, ClipboardProvider, ClipboardType, CommandProvider, CommandSpec, NativeProvider,
+    TermcodeProvider,
+};
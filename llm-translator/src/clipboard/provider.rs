@@ -0,0 +1,594 @@
+use std::env;
+use std::io::{Read, Write};
+use std::process::{Command, Stdio};
+use std::sync::{mpsc, Arc, Mutex as StdMutex};
+use std::time::Duration;
+
+use arboard::Clipboard;
+use base64::Engine;
+
+use super::manager::ClipboardError;
+use crate::config::{ClipboardCommandSpec, ClipboardProviderKind, ClipboardSettings};
+
+/// Which clipboard channel to read/write. `Primary` is the X11/Wayland
+/// middle-click selection, distinct from the `Clipboard` that Ctrl+C/Ctrl+V use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClipboardType {
+    Clipboard,
+    Primary,
+}
+
+/// Abstracts over how clipboard text is read/written so `ClipboardManager`
+/// isn't hard-wired to `arboard`, which fails on headless/Wayland/SSH setups.
+pub trait ClipboardProvider: Send {
+    fn name(&self) -> &'static str;
+    fn get_contents(&mut self, selection: ClipboardType) -> Result<String, ClipboardError>;
+    fn set_contents(&mut self, selection: ClipboardType, text: &str) -> Result<(), ClipboardError>;
+
+    /// Whether `ClipboardType::Primary` reflects a real OS-level selection
+    /// rather than just an in-memory stand-in. Callers can use this to skip
+    /// a synthetic copy keypress when a genuine primary selection is available.
+    fn supports_primary(&self) -> bool {
+        false
+    }
+}
+
+/// Talks to the OS clipboard directly via `arboard`. `arboard` has no notion
+/// of the X11/Wayland primary selection, so `Primary` is backed by a plain
+/// in-memory buffer here rather than the real OS-level selection.
+pub struct NativeProvider {
+    clipboard: Clipboard,
+    primary_buffer: String,
+}
+
+impl NativeProvider {
+    pub fn new() -> Result<Self, ClipboardError> {
+        Ok(Self {
+            clipboard: Clipboard::new().map_err(|e| ClipboardError::AccessError(e.to_string()))?,
+            primary_buffer: String::new(),
+        })
+    }
+}
+
+impl ClipboardProvider for NativeProvider {
+    fn name(&self) -> &'static str {
+        "native"
+    }
+
+    fn get_contents(&mut self, selection: ClipboardType) -> Result<String, ClipboardError> {
+        match selection {
+            ClipboardType::Clipboard => {
+                self.clipboard.get_text().map_err(|e| ClipboardError::AccessError(e.to_string()))
+            }
+            ClipboardType::Primary => Ok(self.primary_buffer.clone()),
+        }
+    }
+
+    fn set_contents(&mut self, selection: ClipboardType, text: &str) -> Result<(), ClipboardError> {
+        match selection {
+            ClipboardType::Clipboard => {
+                self.clipboard.set_text(text).map_err(|e| ClipboardError::AccessError(e.to_string()))
+            }
+            ClipboardType::Primary => {
+                self.primary_buffer = text.to_string();
+                Ok(())
+            }
+        }
+    }
+}
+
+/// One shelled-out command, e.g. `wl-copy` for yank or `xclip -o` for paste.
+#[derive(Debug, Clone)]
+pub struct CommandSpec {
+    pub command: String,
+    pub args: Vec<String>,
+}
+
+impl From<&ClipboardCommandSpec> for CommandSpec {
+    fn from(spec: &ClipboardCommandSpec) -> Self {
+        Self {
+            command: spec.command.clone(),
+            args: spec.args.clone(),
+        }
+    }
+}
+
+/// Shells out to an external tool for copy/paste, for machines where
+/// `arboard` doesn't work: headless Wayland, bare X11 over SSH, tmux-only.
+/// Writes `text` to the yank command's stdin, reads the paste command's
+/// stdout, and bounds the process wait by `timeout`. `primary` is `None`
+/// for tools with no primary-selection command (pbcopy, tmux, custom), in
+/// which case `Primary` falls back to an in-memory buffer.
+pub struct CommandProvider {
+    name: &'static str,
+    clipboard: (CommandSpec, CommandSpec),
+    primary: Option<(CommandSpec, CommandSpec)>,
+    primary_buffer: String,
+    timeout: Duration,
+}
+
+impl CommandProvider {
+    pub fn new(name: &'static str, yank: CommandSpec, paste: CommandSpec, timeout: Duration) -> Self {
+        Self {
+            name,
+            clipboard: (yank, paste),
+            primary: None,
+            primary_buffer: String::new(),
+            timeout,
+        }
+    }
+
+    /// Attach real primary-selection commands, e.g. `xclip -selection primary`.
+    pub fn with_primary(mut self, yank: CommandSpec, paste: CommandSpec) -> Self {
+        self.primary = Some((yank, paste));
+        self
+    }
+
+    fn specs_for(&self, selection: ClipboardType) -> Option<&(CommandSpec, CommandSpec)> {
+        match selection {
+            ClipboardType::Clipboard => Some(&self.clipboard),
+            ClipboardType::Primary => self.primary.as_ref(),
+        }
+    }
+
+    fn run(&self, spec: &CommandSpec, stdin_data: Option<&str>) -> Result<Vec<u8>, ClipboardError> {
+        let mut child = Command::new(&spec.command)
+            .args(&spec.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| ClipboardError::AccessError(format!("{}: {}", spec.command, e)))?;
+
+        if let Some(data) = stdin_data {
+            if let Some(mut stdin) = child.stdin.take() {
+                let _ = stdin.write_all(data.as_bytes());
+            }
+        } else {
+            // Drop our end so the child sees EOF immediately rather than
+            // blocking on a read that will never produce data.
+            drop(child.stdin.take());
+        }
+
+        // Drain stdout on its own thread, independent of the wait below, so
+        // a full pipe buffer can't make the child block on a write while
+        // we're waiting for it to exit.
+        let mut stdout = child.stdout.take();
+        let stdout_handle = std::thread::spawn(move || {
+            let mut buf = Vec::new();
+            if let Some(stdout) = stdout.as_mut() {
+                let _ = stdout.read_to_end(&mut buf);
+            }
+            buf
+        });
+
+        // Kept behind a shared lock (rather than moved into the waiter
+        // thread via `wait_with_output`) so a timeout below can still kill
+        // it instead of leaving it - and the thread blocked on it - running
+        // forever.
+        let child = Arc::new(StdMutex::new(child));
+        let waiter_child = Arc::clone(&child);
+        let (tx, rx) = mpsc::channel();
+        let waiter_handle = std::thread::spawn(move || {
+            let _ = tx.send(waiter_child.lock().unwrap().wait());
+        });
+
+        match rx.recv_timeout(self.timeout) {
+            Ok(Ok(_status)) => Ok(stdout_handle.join().unwrap_or_default()),
+            Ok(Err(e)) => Err(ClipboardError::AccessError(e.to_string())),
+            Err(_) => {
+                // Kill the hung child so the waiter thread's `wait()` (and
+                // the stdout-draining thread, once the closed pipe gives it
+                // EOF) actually complete instead of leaking forever.
+                let _ = child.lock().unwrap().kill();
+                let _ = waiter_handle.join();
+                let _ = stdout_handle.join();
+                Err(ClipboardError::Timeout)
+            }
+        }
+    }
+}
+
+impl ClipboardProvider for CommandProvider {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn get_contents(&mut self, selection: ClipboardType) -> Result<String, ClipboardError> {
+        match self.specs_for(selection) {
+            Some((_, paste)) => {
+                let bytes = self.run(&paste.clone(), None)?;
+                String::from_utf8(bytes).map_err(|e| ClipboardError::AccessError(e.to_string()))
+            }
+            None => Ok(self.primary_buffer.clone()),
+        }
+    }
+
+    fn set_contents(&mut self, selection: ClipboardType, text: &str) -> Result<(), ClipboardError> {
+        match self.specs_for(selection) {
+            Some((yank, _)) => {
+                self.run(&yank.clone(), Some(text))?;
+                Ok(())
+            }
+            None => {
+                self.primary_buffer = text.to_string();
+                Ok(())
+            }
+        }
+    }
+
+    fn supports_primary(&self) -> bool {
+        self.primary.is_some()
+    }
+}
+
+/// Practical payload limit a number of terminals (xterm, iTerm2's default)
+/// enforce on OSC 52 sequences; oversized text is rejected rather than
+/// silently truncated.
+const OSC52_MAX_PAYLOAD_BYTES: usize = 74 * 1024;
+
+/// Sets the clipboard by emitting an OSC 52 escape sequence
+/// (`ESC ] 52 ; c ; <base64> BEL`) straight to the terminal, which forwards
+/// it to the user's real desktop clipboard. This works over SSH or inside a
+/// multiplexer where no local clipboard tool is reachable. Few terminals
+/// answer the OSC 52 query form reliably, so reads are served from the last
+/// value we wrote rather than actually querying the terminal.
+pub struct TermcodeProvider {
+    in_tmux: bool,
+    last_written: String,
+    primary_buffer: String,
+}
+
+impl TermcodeProvider {
+    pub fn new() -> Self {
+        Self {
+            in_tmux: env::var_os("TMUX").is_some(),
+            last_written: String::new(),
+            primary_buffer: String::new(),
+        }
+    }
+
+    /// tmux intercepts OSC sequences meant for the outer terminal unless
+    /// they're wrapped in its passthrough envelope, and any literal ESC
+    /// inside the payload must be doubled so tmux doesn't treat it as the
+    /// envelope's own terminator.
+    fn wrap_for_tmux(&self, sequence: &str) -> String {
+        if self.in_tmux {
+            format!("\x1bPtmux;{}\x1b\\", sequence.replace('\x1b', "\x1b\x1b"))
+        } else {
+            sequence.to_string()
+        }
+    }
+}
+
+impl Default for TermcodeProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ClipboardProvider for TermcodeProvider {
+    fn name(&self) -> &'static str {
+        "termcode"
+    }
+
+    fn get_contents(&mut self, selection: ClipboardType) -> Result<String, ClipboardError> {
+        match selection {
+            ClipboardType::Clipboard => Ok(self.last_written.clone()),
+            ClipboardType::Primary => Ok(self.primary_buffer.clone()),
+        }
+    }
+
+    fn set_contents(&mut self, selection: ClipboardType, text: &str) -> Result<(), ClipboardError> {
+        if selection == ClipboardType::Primary {
+            // OSC 52 has no primary-selection form.
+            self.primary_buffer = text.to_string();
+            return Ok(());
+        }
+
+        if text.len() > OSC52_MAX_PAYLOAD_BYTES {
+            return Err(ClipboardError::AccessError(format!(
+                "text is {} bytes, exceeds the ~{}KB OSC 52 payload limit",
+                text.len(),
+                OSC52_MAX_PAYLOAD_BYTES / 1024,
+            )));
+        }
+
+        let encoded = base64::engine::general_purpose::STANDARD.encode(text.as_bytes());
+        let sequence = self.wrap_for_tmux(&format!("\x1b]52;c;{}\x07", encoded));
+
+        std::io::stdout()
+            .write_all(sequence.as_bytes())
+            .and_then(|_| std::io::stdout().flush())
+            .map_err(|e| ClipboardError::AccessError(e.to_string()))?;
+
+        self.last_written = text.to_string();
+        Ok(())
+    }
+}
+
+/// A `which`-style lookup: is `bin` an executable file somewhere on `PATH`?
+fn on_path(bin: &str) -> bool {
+    let Some(path_var) = env::var_os("PATH") else { return false };
+    env::split_paths(&path_var).any(|dir| dir.join(bin).is_file())
+}
+
+fn wayland_active() -> bool {
+    env::var_os("WAYLAND_DISPLAY").is_some()
+}
+
+fn x11_active() -> bool {
+    env::var_os("DISPLAY").is_some()
+}
+
+fn wayland_provider(timeout: Duration) -> CommandProvider {
+    CommandProvider::new(
+        "wayland",
+        CommandSpec { command: "wl-copy".to_string(), args: vec![] },
+        CommandSpec { command: "wl-paste".to_string(), args: vec!["-n".to_string()] },
+        timeout,
+    )
+    .with_primary(
+        CommandSpec { command: "wl-copy".to_string(), args: vec!["--primary".to_string()] },
+        CommandSpec {
+            command: "wl-paste".to_string(),
+            args: vec!["--primary".to_string(), "-n".to_string()],
+        },
+    )
+}
+
+fn xclip_provider(timeout: Duration) -> CommandProvider {
+    CommandProvider::new(
+        "xclip",
+        CommandSpec {
+            command: "xclip".to_string(),
+            args: vec!["-selection".to_string(), "clipboard".to_string()],
+        },
+        CommandSpec {
+            command: "xclip".to_string(),
+            args: vec!["-selection".to_string(), "clipboard".to_string(), "-o".to_string()],
+        },
+        timeout,
+    )
+    .with_primary(
+        CommandSpec {
+            command: "xclip".to_string(),
+            args: vec!["-selection".to_string(), "primary".to_string()],
+        },
+        CommandSpec {
+            command: "xclip".to_string(),
+            args: vec!["-selection".to_string(), "primary".to_string(), "-o".to_string()],
+        },
+    )
+}
+
+fn xsel_provider(timeout: Duration) -> CommandProvider {
+    CommandProvider::new(
+        "xsel",
+        CommandSpec {
+            command: "xsel".to_string(),
+            args: vec!["--clipboard".to_string(), "--input".to_string()],
+        },
+        CommandSpec {
+            command: "xsel".to_string(),
+            args: vec!["--clipboard".to_string(), "--output".to_string()],
+        },
+        timeout,
+    )
+    .with_primary(
+        CommandSpec {
+            command: "xsel".to_string(),
+            args: vec!["--primary".to_string(), "--input".to_string()],
+        },
+        CommandSpec {
+            command: "xsel".to_string(),
+            args: vec!["--primary".to_string(), "--output".to_string()],
+        },
+    )
+}
+
+fn pbcopy_provider(timeout: Duration) -> CommandProvider {
+    CommandProvider::new(
+        "pbcopy",
+        CommandSpec { command: "pbcopy".to_string(), args: vec![] },
+        CommandSpec { command: "pbpaste".to_string(), args: vec![] },
+        timeout,
+    )
+}
+
+fn tmux_provider(timeout: Duration) -> CommandProvider {
+    CommandProvider::new(
+        "tmux",
+        CommandSpec {
+            command: "tmux".to_string(),
+            args: vec!["load-buffer".to_string(), "-".to_string()],
+        },
+        CommandSpec {
+            command: "tmux".to_string(),
+            args: vec!["save-buffer".to_string(), "-".to_string()],
+        },
+        timeout,
+    )
+}
+
+/// Priority chain: Wayland tools under Wayland, then X11 tools under
+/// `DISPLAY`, then the platform native, falling back to native `arboard`.
+fn detect_provider(timeout: Duration) -> Result<Box<dyn ClipboardProvider>, ClipboardError> {
+    if wayland_active() && on_path("wl-copy") && on_path("wl-paste") {
+        return Ok(Box::new(wayland_provider(timeout)));
+    }
+
+    if x11_active() {
+        if on_path("xclip") {
+            return Ok(Box::new(xclip_provider(timeout)));
+        }
+        if on_path("xsel") {
+            return Ok(Box::new(xsel_provider(timeout)));
+        }
+    }
+
+    if cfg!(target_os = "macos") && on_path("pbcopy") && on_path("pbpaste") {
+        return Ok(Box::new(pbcopy_provider(timeout)));
+    }
+
+    Ok(Box::new(NativeProvider::new()?))
+}
+
+/// Build the provider named by `settings.provider`, auto-detecting one when
+/// it's `Auto`.
+pub fn build_provider(
+    settings: &ClipboardSettings,
+    timeout_ms: u64,
+) -> Result<Box<dyn ClipboardProvider>, ClipboardError> {
+    let timeout = Duration::from_millis(timeout_ms);
+
+    match settings.provider {
+        ClipboardProviderKind::Auto => detect_provider(timeout),
+        ClipboardProviderKind::Native => Ok(Box::new(NativeProvider::new()?)),
+        ClipboardProviderKind::Wayland => Ok(Box::new(wayland_provider(timeout))),
+        ClipboardProviderKind::Xclip => Ok(Box::new(xclip_provider(timeout))),
+        ClipboardProviderKind::Xsel => Ok(Box::new(xsel_provider(timeout))),
+        ClipboardProviderKind::Pbcopy => Ok(Box::new(pbcopy_provider(timeout))),
+        ClipboardProviderKind::Tmux => Ok(Box::new(tmux_provider(timeout))),
+        ClipboardProviderKind::Termcode => Ok(Box::new(TermcodeProvider::new())),
+        ClipboardProviderKind::Custom => {
+            let custom = settings.custom.as_ref().ok_or_else(|| {
+                ClipboardError::AccessError(
+                    "clipboard.provider = \"custom\" requires a [clipboard.custom] yank/paste command".to_string(),
+                )
+            })?;
+            Ok(Box::new(CommandProvider::new(
+                "custom",
+                CommandSpec::from(&custom.yank),
+                CommandSpec::from(&custom.paste),
+                timeout,
+            )))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_on_path_finds_a_common_binary() {
+        // `sh` is about as close to universally-on-PATH as it gets in this sandbox.
+        assert!(on_path("sh"));
+    }
+
+    #[test]
+    fn test_on_path_rejects_nonexistent_binary() {
+        assert!(!on_path("definitely-not-a-real-binary-xyz"));
+    }
+
+    #[test]
+    fn test_build_provider_custom_requires_command() {
+        let settings = ClipboardSettings {
+            provider: ClipboardProviderKind::Custom,
+            custom: None,
+        };
+
+        let result = build_provider(&settings, 500);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_build_provider_custom_uses_configured_commands() {
+        let settings = ClipboardSettings {
+            provider: ClipboardProviderKind::Custom,
+            custom: Some(crate::config::CustomClipboardCommand {
+                yank: ClipboardCommandSpec { command: "cat".to_string(), args: vec![] },
+                paste: ClipboardCommandSpec {
+                    command: "echo".to_string(),
+                    args: vec!["hello".to_string()],
+                },
+            }),
+        };
+
+        let mut provider = build_provider(&settings, 500).unwrap();
+        assert_eq!(provider.name(), "custom");
+        assert_eq!(provider.get_contents(ClipboardType::Clipboard).unwrap().trim(), "hello");
+    }
+
+    #[test]
+    fn test_command_provider_set_contents_pipes_to_yank_command() {
+        // `cat` with no args reads stdin to EOF and exits cleanly - a
+        // convenient stand-in for a real clipboard tool in this sandboxed
+        // test. Only the write side is exercised here.
+        let mut provider = CommandProvider::new(
+            "test",
+            CommandSpec { command: "cat".to_string(), args: vec![] },
+            CommandSpec { command: "cat".to_string(), args: vec![] },
+            Duration::from_secs(2),
+        );
+
+        provider.set_contents(ClipboardType::Clipboard, "hello, clipboard").unwrap();
+    }
+
+    #[test]
+    fn test_command_provider_times_out_on_hanging_process() {
+        let mut provider = CommandProvider::new(
+            "test",
+            CommandSpec { command: "sleep".to_string(), args: vec!["5".to_string()] },
+            CommandSpec { command: "sleep".to_string(), args: vec!["5".to_string()] },
+            Duration::from_millis(50),
+        );
+
+        assert!(matches!(
+            provider.get_contents(ClipboardType::Clipboard),
+            Err(ClipboardError::Timeout)
+        ));
+    }
+
+    #[test]
+    fn test_command_provider_without_primary_falls_back_to_buffer() {
+        let mut provider = CommandProvider::new(
+            "test",
+            CommandSpec { command: "cat".to_string(), args: vec![] },
+            CommandSpec { command: "cat".to_string(), args: vec![] },
+            Duration::from_secs(2),
+        );
+
+        assert!(!provider.supports_primary());
+        provider.set_contents(ClipboardType::Primary, "middle-click text").unwrap();
+        assert_eq!(provider.get_contents(ClipboardType::Primary).unwrap(), "middle-click text");
+    }
+
+    #[test]
+    fn test_command_provider_with_primary_reports_support() {
+        let provider = xclip_provider(Duration::from_secs(1));
+        assert!(provider.supports_primary());
+    }
+
+    #[test]
+    fn test_termcode_provider_roundtrips_last_written() {
+        let mut provider = TermcodeProvider::new();
+        provider.set_contents(ClipboardType::Clipboard, "hello over ssh").unwrap();
+        assert_eq!(provider.get_contents(ClipboardType::Clipboard).unwrap(), "hello over ssh");
+    }
+
+    #[test]
+    fn test_termcode_provider_rejects_oversized_payload() {
+        let mut provider = TermcodeProvider::new();
+        let huge = "x".repeat(OSC52_MAX_PAYLOAD_BYTES + 1);
+        assert!(provider.set_contents(ClipboardType::Clipboard, &huge).is_err());
+    }
+
+    #[test]
+    fn test_termcode_provider_wraps_for_tmux() {
+        let mut provider = TermcodeProvider::new();
+        provider.in_tmux = true;
+        let wrapped = provider.wrap_for_tmux("\x1b]52;c;QQ==\x07");
+        assert!(wrapped.starts_with("\x1bPtmux;"));
+        assert!(wrapped.ends_with("\x1b\\"));
+    }
+
+    #[test]
+    fn test_termcode_provider_primary_is_independent_in_memory_buffer() {
+        let mut provider = TermcodeProvider::new();
+        provider.set_contents(ClipboardType::Primary, "middle-click").unwrap();
+        assert_eq!(provider.get_contents(ClipboardType::Primary).unwrap(), "middle-click");
+        assert_eq!(provider.get_contents(ClipboardType::Clipboard).unwrap(), "");
+    }
+}
@@ -1,6 +1,9 @@
 pub mod settings;
+pub mod secrets;
 
 pub use settings::{
     Config, GeneralSettings, HotkeySettings, ApiSettings, PromptSettings,
-    PromptPreset, LimitSettings, ValidationSettings, BehaviorSettings, ConfigError
-};
\ No newline at end of file
+    PromptPreset, LimitSettings, ValidationSettings, BehaviorSettings, ConfigError, ProviderKind,
+    ClipboardSettings, ClipboardProviderKind, ClipboardCommandSpec, CustomClipboardCommand,
+};
+pub use secrets::SecretStore;
\ No newline at end of file
@@ -0,0 +1,41 @@
+use keyring::Entry;
+
+use super::settings::ConfigError;
+
+const SERVICE_NAME: &str = "llm-translator";
+
+/// Stores the API key in the OS credential store (Keychain on macOS,
+/// Credential Manager on Windows, the Secret Service/kwallet on Linux)
+/// instead of the config TOML, keyed by `endpoint+model` so switching
+/// providers doesn't clobber another provider's saved key.
+pub struct SecretStore;
+
+impl SecretStore {
+    fn entry(endpoint: &str, model: &str) -> Result<Entry, ConfigError> {
+        let account = format!("{}::{}", endpoint, model);
+        Entry::new(SERVICE_NAME, &account).map_err(|e| ConfigError::KeyringError(e.to_string()))
+    }
+
+    /// Read the stored key, if any. `Ok(None)` means no secret has been
+    /// saved yet for this endpoint+model, not an error.
+    pub fn load(endpoint: &str, model: &str) -> Result<Option<String>, ConfigError> {
+        match Self::entry(endpoint, model)?.get_password() {
+            Ok(key) => Ok(Some(key)),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(e) => Err(ConfigError::KeyringError(e.to_string())),
+        }
+    }
+
+    pub fn save(endpoint: &str, model: &str, key: &str) -> Result<(), ConfigError> {
+        Self::entry(endpoint, model)?
+            .set_password(key)
+            .map_err(|e| ConfigError::KeyringError(e.to_string()))
+    }
+
+    pub fn delete(endpoint: &str, model: &str) -> Result<(), ConfigError> {
+        match Self::entry(endpoint, model)?.delete_password() {
+            Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(e) => Err(ConfigError::KeyringError(e.to_string())),
+        }
+    }
+}
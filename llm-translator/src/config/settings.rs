@@ -21,6 +21,9 @@ pub enum ConfigError {
     
     #[error("Config directory not found")]
     DirectoryNotFound,
+
+    #[error("Keyring error: {0}")]
+    KeyringError(String),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
@@ -46,6 +49,9 @@ pub struct Config {
     
     #[serde(default)]
     pub behavior: BehaviorSettings,
+
+    #[serde(default)]
+    pub clipboard: ClipboardSettings,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -61,26 +67,99 @@ pub struct HotkeySettings {
     pub alternatives: Vec<String>,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProviderKind {
+    /// OpenAI-compatible `/v1/chat/completions` dialect (OpenAI, Ollama, custom endpoints)
+    OpenAi,
+    /// Anthropic's Messages API
+    Anthropic,
+}
+
+impl Default for ProviderKind {
+    fn default() -> Self {
+        ProviderKind::OpenAi
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
 pub struct ApiSettings {
     #[validate(url)]
     pub endpoint: String,
-    
+
     pub model: String,
-    
+
     #[validate(range(min = 0.0, max = 2.0))]
     pub temperature: f32,
-    
+
     #[validate(range(min = 1, max = 10))]
     pub max_retries: u32,
-    
+
     #[validate(range(min = 5, max = 300))]
     pub timeout_seconds: u64,
-    
+
+    #[serde(default)]
+    pub provider: ProviderKind,
+
+    /// Consecutive failures before the circuit breaker opens.
+    #[validate(range(min = 1, max = 20))]
+    #[serde(default = "default_circuit_breaker_threshold")]
+    pub circuit_breaker_threshold: u32,
+
+    /// How long the breaker stays open before allowing a half-open probe.
+    #[validate(range(min = 1, max = 3600))]
+    #[serde(default = "default_circuit_breaker_cooldown_seconds")]
+    pub circuit_breaker_cooldown_seconds: u64,
+
+    /// Shared retry token bucket capacity, capping total in-flight retries
+    /// across every concurrent translation.
+    #[validate(range(min = 1, max = 100_000))]
+    #[serde(default = "default_retry_bucket_capacity")]
+    pub retry_bucket_capacity: u32,
+
+    /// Tokens charged for retrying a timeout/transport/service-unavailable error.
+    #[validate(range(min = 1, max = 1000))]
+    #[serde(default = "default_retry_bucket_timeout_cost")]
+    pub retry_bucket_timeout_cost: u32,
+
+    /// Tokens charged for retrying a rate-limited (429) error.
+    #[validate(range(min = 1, max = 1000))]
+    #[serde(default = "default_retry_bucket_throttle_cost")]
+    pub retry_bucket_throttle_cost: u32,
+
+    /// Tokens refunded to the bucket on each fully successful request.
+    #[validate(range(min = 0, max = 1000))]
+    #[serde(default = "default_retry_bucket_refund")]
+    pub retry_bucket_refund: u32,
+
     #[serde(skip)]
     pub api_key: Option<String>,
 }
 
+fn default_circuit_breaker_threshold() -> u32 {
+    5
+}
+
+fn default_circuit_breaker_cooldown_seconds() -> u64 {
+    30
+}
+
+fn default_retry_bucket_capacity() -> u32 {
+    500
+}
+
+fn default_retry_bucket_timeout_cost() -> u32 {
+    5
+}
+
+fn default_retry_bucket_throttle_cost() -> u32 {
+    10
+}
+
+fn default_retry_bucket_refund() -> u32 {
+    1
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PromptSettings {
     pub active_preset: String,
@@ -128,6 +207,59 @@ pub struct BehaviorSettings {
     pub preserve_clipboard: bool,
     pub show_length_warning: bool,
     pub auto_split_long_text: bool,
+
+    /// Seconds after which the clipboard is auto-wiped following an
+    /// ephemeral write (e.g. a translated selection). `0` disables it.
+    #[serde(default)]
+    pub clipboard_autoclear_seconds: u64,
+}
+
+/// Which backend `ClipboardManager` should use to read/write the clipboard.
+/// `Auto` probes the environment (Wayland tools, then X11 tools, then the
+/// platform native) instead of calling `arboard` directly, which fails on
+/// headless/Wayland/SSH machines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ClipboardProviderKind {
+    Auto,
+    Native,
+    Wayland,
+    Xclip,
+    Xsel,
+    Pbcopy,
+    Tmux,
+    Termcode,
+    Custom,
+}
+
+impl Default for ClipboardProviderKind {
+    fn default() -> Self {
+        ClipboardProviderKind::Auto
+    }
+}
+
+/// One shelled-out command, e.g. `{ command = "cat", args = ["file"] }`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClipboardCommandSpec {
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+}
+
+/// Yank/paste commands for `ClipboardProviderKind::Custom`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomClipboardCommand {
+    pub yank: ClipboardCommandSpec,
+    pub paste: ClipboardCommandSpec,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClipboardSettings {
+    #[serde(default)]
+    pub provider: ClipboardProviderKind,
+
+    #[serde(default)]
+    pub custom: Option<CustomClipboardCommand>,
 }
 
 // Default implementations
@@ -161,6 +293,13 @@ impl Default for ApiSettings {
             temperature: 0.3,
             max_retries: 3,
             timeout_seconds: 30,
+            provider: ProviderKind::default(),
+            circuit_breaker_threshold: default_circuit_breaker_threshold(),
+            circuit_breaker_cooldown_seconds: default_circuit_breaker_cooldown_seconds(),
+            retry_bucket_capacity: default_retry_bucket_capacity(),
+            retry_bucket_timeout_cost: default_retry_bucket_timeout_cost(),
+            retry_bucket_throttle_cost: default_retry_bucket_throttle_cost(),
+            retry_bucket_refund: default_retry_bucket_refund(),
             api_key: None,
         }
     }
@@ -237,6 +376,16 @@ impl Default for BehaviorSettings {
             preserve_clipboard: true,
             show_length_warning: true,
             auto_split_long_text: false,
+            clipboard_autoclear_seconds: 0,
+        }
+    }
+}
+
+impl Default for ClipboardSettings {
+    fn default() -> Self {
+        Self {
+            provider: ClipboardProviderKind::default(),
+            custom: None,
         }
     }
 }
@@ -251,6 +400,7 @@ impl Default for Config {
             limits: LimitSettings::default(),
             validation: ValidationSettings::default(),
             behavior: BehaviorSettings::default(),
+            clipboard: ClipboardSettings::default(),
         }
     }
 }
@@ -268,17 +418,63 @@ impl Config {
         
         let contents = fs::read_to_string(&config_path)?;
         let mut config: Config = toml::from_str(&contents)?;
-        
-        // Load API key from secure storage (keyring)
-        // This will be implemented when we add keyring support
-        
+
+        // One-time migration: an API key may show up via an environment
+        // variable, or as a plaintext `api_key` left in an older config.toml
+        // from before that field was marked `#[serde(skip)]` (so `config`
+        // above never actually picked it up). If the keyring doesn't have a
+        // key yet for this endpoint+model, import whichever of the two we
+        // find - preferring the legacy file since its presence means it was
+        // the one actually in use - and scrub it from disk.
+        if config.load_api_key()?.is_none() {
+            let legacy_key = Self::legacy_toml_api_key(&contents);
+            if let Some(key) = legacy_key.or_else(Self::env_api_key) {
+                config.save_api_key(&key)?;
+                config.save()?;
+            }
+        }
+
+        config.api.api_key = config.load_api_key()?;
+
         // Validate the config
         config.validate().map_err(|e| {
             ConfigError::ValidationError(e.to_string())
         })?;
-        
+
         Ok(config)
     }
+
+    fn env_api_key() -> Option<String> {
+        std::env::var("OPENAI_API_KEY")
+            .or_else(|_| std::env::var("LLM_API_KEY"))
+            .ok()
+            .filter(|key| !key.is_empty())
+    }
+
+    /// Pull a plaintext `api_key` out of the raw config file contents, if
+    /// one is still there from before the field was `#[serde(skip)]`d -
+    /// `toml::from_str::<Config>` silently drops it, so it has to be read
+    /// back out of the raw document instead.
+    fn legacy_toml_api_key(contents: &str) -> Option<String> {
+        let value: toml::Value = toml::from_str(contents).ok()?;
+        value
+            .get("api")?
+            .get("api_key")?
+            .as_str()
+            .map(str::to_string)
+            .filter(|key| !key.is_empty())
+    }
+
+    /// Read the API key from the OS keyring for this config's endpoint+model.
+    /// `Ok(None)` means no secret has been stored yet.
+    pub fn load_api_key(&self) -> Result<Option<String>, ConfigError> {
+        crate::config::SecretStore::load(&self.api.endpoint, &self.api.model)
+    }
+
+    /// Save the API key to the OS keyring for this config's endpoint+model.
+    pub fn save_api_key(&self, key: &str) -> Result<(), ConfigError> {
+        crate::config::SecretStore::save(&self.api.endpoint, &self.api.model, key)
+    }
     
     pub fn save(&self) -> Result<(), ConfigError> {
         let config_path = Self::config_path()?;
@@ -306,7 +502,17 @@ impl Config {
     }
     
     pub fn validate(&self) -> Result<(), validator::ValidationErrors> {
-        <Self as Validate>::validate(self)
+        <Self as Validate>::validate(self)?;
+
+        if let Err(e) = crate::hotkeys::validate_hotkey_settings(&self.hotkey) {
+            let mut errors = validator::ValidationErrors::new();
+            let mut hotkey_error = ValidationError::new("invalid_hotkey");
+            hotkey_error.message = Some(e.to_string().into());
+            errors.add("hotkey", hotkey_error);
+            return Err(errors);
+        }
+
+        Ok(())
     }
     
     /// Get the currently active prompt
@@ -389,6 +595,22 @@ mod tests {
         config.api.endpoint = "not-a-url".to_string();
         assert!(config.validate().is_err());
     }
+
+    #[test]
+    fn test_config_validation_catches_duplicate_hotkey_binding() {
+        let mut config = Config::default();
+        config.hotkey.alternatives = vec![config.hotkey.translate.clone()];
+
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_config_validation_catches_malformed_hotkey() {
+        let mut config = Config::default();
+        config.hotkey.cancel = "Ctrl+Shift+".to_string();
+
+        assert!(config.validate().is_err());
+    }
     
     #[test]
     fn test_config_serialization() {
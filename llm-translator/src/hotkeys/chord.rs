@@ -0,0 +1,377 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+
+use super::validator::{HotkeyValidationError, KeyCombo, Platform};
+
+/// Default time a [`ChordMatcher`] waits for the next chord in a sequence
+/// before giving up and resetting to the root. Override with
+/// [`ChordMatcher::with_timeout`].
+const DEFAULT_CHORD_TIMEOUT: Duration = Duration::from_millis(800);
+
+/// An ordered sequence of chords pressed one after another, e.g.
+/// `Ctrl+K Ctrl+T`. Parsed from a string with one [`KeyCombo`] per
+/// whitespace-separated segment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyChord(pub Vec<KeyCombo>);
+
+impl KeyChord {
+    pub fn from_string(spec: &str) -> Result<Self, HotkeyValidationError> {
+        let combos = spec
+            .split_whitespace()
+            .map(KeyCombo::from_string)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if combos.is_empty() {
+            return Err(HotkeyValidationError::InvalidFormat(spec.to_string()));
+        }
+
+        Ok(Self(combos))
+    }
+
+    /// Canonical `"Ctrl+K Ctrl+T"` word form, independent of the host
+    /// platform so a saved config stays portable.
+    fn canonical_string(&self) -> String {
+        self.0
+            .iter()
+            .map(|combo| combo.to_display_string(Platform::Windows))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+impl fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.canonical_string())
+    }
+}
+
+impl Serialize for KeyChord {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.canonical_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for KeyChord {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let spec = String::deserialize(deserializer)?;
+        KeyChord::from_string(&spec).map_err(D::Error::custom)
+    }
+}
+
+#[derive(Default)]
+struct TrieNode {
+    children: HashMap<KeyCombo, TrieNode>,
+    action: Option<String>,
+}
+
+enum WalkResult {
+    NoMatch,
+    Pending,
+    Matched(String),
+}
+
+/// Prefix trie of registered [`KeyChord`]s, each path of [`KeyCombo`] edges
+/// ending in a leaf carrying the bound action id.
+#[derive(Default)]
+pub struct ChordTrie {
+    root: TrieNode,
+}
+
+impl ChordTrie {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register `chord` under `action`.
+    ///
+    /// A chord that is itself a prefix of an already-registered chord (or
+    /// vice versa) is rejected here rather than allowed to silently shadow
+    /// one another - e.g. once `Ctrl+K` is bound on its own, `Ctrl+K Ctrl+T`
+    /// can never resolve, and once `Ctrl+K Ctrl+T` is bound, `Ctrl+K` alone
+    /// would never reach a leaf. Pick non-overlapping chords instead.
+    pub fn register(
+        &mut self,
+        chord: &KeyChord,
+        action: impl Into<String>,
+    ) -> Result<(), HotkeyValidationError> {
+        let mut node = &mut self.root;
+        for combo in &chord.0 {
+            if node.action.is_some() {
+                return Err(HotkeyValidationError::DuplicateBinding(
+                    "chord is an extension of a shorter chord already bound to an action".to_string(),
+                ));
+            }
+            node = node.children.entry(combo.clone()).or_default();
+        }
+
+        if node.action.is_some() || !node.children.is_empty() {
+            return Err(HotkeyValidationError::DuplicateBinding(
+                "chord is a prefix of a longer chord already bound to an action".to_string(),
+            ));
+        }
+
+        node.action = Some(action.into());
+        Ok(())
+    }
+
+    fn walk(&self, path: &[KeyCombo]) -> WalkResult {
+        let mut node = &self.root;
+        for combo in path {
+            match node.children.get(combo) {
+                Some(next) => node = next,
+                None => return WalkResult::NoMatch,
+            }
+        }
+
+        match &node.action {
+            Some(action) => WalkResult::Matched(action.clone()),
+            None if !node.children.is_empty() => WalkResult::Pending,
+            None => WalkResult::NoMatch,
+        }
+    }
+}
+
+/// Outcome of feeding one [`KeyCombo`] into a [`ChordMatcher`] walk.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChordMatch {
+    /// No registered chord starts with the combos seen so far.
+    NoMatch,
+    /// A valid prefix of one or more registered chords - the matcher is
+    /// armed with a pending timeout and will keep consuming combos.
+    Pending,
+    /// A full chord resolved to this action id.
+    Matched(String),
+}
+
+/// Walks a [`ChordTrie`] one [`KeyCombo`] at a time as hotkey events arrive,
+/// resetting back to the root once the pending timeout elapses without the
+/// sequence resolving.
+///
+/// The trie is passed into [`ChordMatcher::feed`] rather than owned by the
+/// matcher, since the set of registered sequences can grow over the
+/// listener's lifetime while a single matcher keeps walking it.
+pub struct ChordMatcher {
+    path: Vec<KeyCombo>,
+    deadline: Option<Instant>,
+    timeout: Duration,
+}
+
+impl ChordMatcher {
+    pub fn new() -> Self {
+        Self {
+            path: Vec::new(),
+            deadline: None,
+            timeout: DEFAULT_CHORD_TIMEOUT,
+        }
+    }
+
+    /// Override how long the matcher waits for the next chord before
+    /// resetting to the root.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Feed the next pressed combo into a walk of `trie`. If the pending
+    /// timeout from a previous call has elapsed, the cursor resets to the
+    /// root before `combo` is considered.
+    pub fn feed(&mut self, trie: &ChordTrie, combo: KeyCombo) -> ChordMatch {
+        if self.deadline.is_some_and(|deadline| Instant::now() > deadline) {
+            self.path.clear();
+        }
+
+        self.path.push(combo);
+
+        match trie.walk(&self.path) {
+            WalkResult::NoMatch => {
+                self.path.clear();
+                self.deadline = None;
+                ChordMatch::NoMatch
+            }
+            WalkResult::Pending => {
+                self.deadline = Some(Instant::now() + self.timeout);
+                ChordMatch::Pending
+            }
+            WalkResult::Matched(action) => {
+                self.path.clear();
+                self.deadline = None;
+                ChordMatch::Matched(action)
+            }
+        }
+    }
+
+    /// Reset the walk cursor back to the root, e.g. driven by an external
+    /// timer that fires once the pending timeout expires rather than
+    /// discovering it lazily on the next `feed`.
+    pub fn reset(&mut self) {
+        self.path.clear();
+        self.deadline = None;
+    }
+}
+
+impl Default for ChordMatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use global_hotkey::hotkey::{Code, Modifiers};
+
+    fn combo(modifiers: Modifiers, key: Code) -> KeyCombo {
+        KeyCombo::new(modifiers, key)
+    }
+
+    #[test]
+    fn test_key_chord_from_string_parses_each_segment() {
+        let chord = KeyChord::from_string("Ctrl+K Ctrl+T").unwrap();
+        assert_eq!(
+            chord.0,
+            vec![
+                combo(Modifiers::CONTROL, Code::KeyK),
+                combo(Modifiers::CONTROL, Code::KeyT),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_key_chord_from_string_rejects_empty() {
+        assert!(KeyChord::from_string("").is_err());
+        assert!(KeyChord::from_string("   ").is_err());
+    }
+
+    #[test]
+    fn test_key_chord_serializes_to_canonical_word_form() {
+        let chord = KeyChord::from_string("Ctrl+K Ctrl+T").unwrap();
+        let json = serde_json::to_string(&chord).unwrap();
+        assert_eq!(json, "\"Ctrl+K Ctrl+T\"");
+    }
+
+    #[test]
+    fn test_key_chord_deserializes_via_from_string() {
+        let chord: KeyChord = serde_json::from_str("\"Ctrl+K Ctrl+T\"").unwrap();
+        assert_eq!(chord, KeyChord::from_string("Ctrl+K Ctrl+T").unwrap());
+    }
+
+    #[test]
+    fn test_key_chord_deserialize_rejects_invalid_string() {
+        let result: Result<KeyChord, _> = serde_json::from_str("\"\"");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_chord_trie_matches_two_key_sequence() {
+        let mut trie = ChordTrie::new();
+        let chord = KeyChord::from_string("Ctrl+K Ctrl+T").unwrap();
+        trie.register(&chord, "translate").unwrap();
+
+        let mut matcher = ChordMatcher::new();
+        assert_eq!(
+            matcher.feed(&trie, combo(Modifiers::CONTROL, Code::KeyK)),
+            ChordMatch::Pending
+        );
+        assert_eq!(
+            matcher.feed(&trie, combo(Modifiers::CONTROL, Code::KeyT)),
+            ChordMatch::Matched("translate".to_string())
+        );
+    }
+
+    #[test]
+    fn test_chord_trie_no_match_resets_path() {
+        let mut trie = ChordTrie::new();
+        let chord = KeyChord::from_string("Ctrl+K Ctrl+T").unwrap();
+        trie.register(&chord, "translate").unwrap();
+
+        let mut matcher = ChordMatcher::new();
+        assert_eq!(
+            matcher.feed(&trie, combo(Modifiers::CONTROL, Code::KeyK)),
+            ChordMatch::Pending
+        );
+        assert_eq!(
+            matcher.feed(&trie, combo(Modifiers::ALT, Code::KeyZ)),
+            ChordMatch::NoMatch
+        );
+
+        // The failed walk cleared the path, so this combo alone decides the
+        // next walk rather than being appended to the dead prefix.
+        assert_eq!(
+            matcher.feed(&trie, combo(Modifiers::CONTROL, Code::KeyK)),
+            ChordMatch::Pending
+        );
+    }
+
+    #[test]
+    fn test_chord_trie_rejects_prefix_of_longer_chord() {
+        let mut trie = ChordTrie::new();
+        let long_chord = KeyChord::from_string("Ctrl+K Ctrl+T").unwrap();
+        trie.register(&long_chord, "translate").unwrap();
+
+        let short_chord = KeyChord::from_string("Ctrl+K").unwrap();
+        assert!(matches!(
+            trie.register(&short_chord, "other"),
+            Err(HotkeyValidationError::DuplicateBinding(_))
+        ));
+    }
+
+    #[test]
+    fn test_chord_trie_rejects_extension_of_shorter_chord() {
+        let mut trie = ChordTrie::new();
+        let short_chord = KeyChord::from_string("Ctrl+K").unwrap();
+        trie.register(&short_chord, "other").unwrap();
+
+        let long_chord = KeyChord::from_string("Ctrl+K Ctrl+T").unwrap();
+        assert!(matches!(
+            trie.register(&long_chord, "translate"),
+            Err(HotkeyValidationError::DuplicateBinding(_))
+        ));
+    }
+
+    #[test]
+    fn test_chord_matcher_timeout_resets_cursor() {
+        let mut trie = ChordTrie::new();
+        let chord = KeyChord::from_string("Ctrl+K Ctrl+T").unwrap();
+        trie.register(&chord, "translate").unwrap();
+
+        let mut matcher = ChordMatcher::new();
+        assert_eq!(
+            matcher.feed(&trie, combo(Modifiers::CONTROL, Code::KeyK)),
+            ChordMatch::Pending
+        );
+
+        // Simulate the pending timeout elapsing.
+        matcher.deadline = Some(Instant::now() - Duration::from_millis(1));
+
+        // Ctrl+T alone isn't registered, so a reset walk reports no match.
+        assert_eq!(
+            matcher.feed(&trie, combo(Modifiers::CONTROL, Code::KeyT)),
+            ChordMatch::NoMatch
+        );
+    }
+
+    #[test]
+    fn test_chord_matcher_with_timeout_overrides_default() {
+        let mut trie = ChordTrie::new();
+        let chord = KeyChord::from_string("Ctrl+K Ctrl+T").unwrap();
+        trie.register(&chord, "translate").unwrap();
+
+        let mut matcher = ChordMatcher::new().with_timeout(Duration::from_millis(50));
+        assert_eq!(
+            matcher.feed(&trie, combo(Modifiers::CONTROL, Code::KeyK)),
+            ChordMatch::Pending
+        );
+
+        std::thread::sleep(Duration::from_millis(60));
+
+        // The shorter timeout has already elapsed, so the cursor reset
+        // before this combo was considered - it doesn't complete the chord.
+        assert_eq!(
+            matcher.feed(&trie, combo(Modifiers::CONTROL, Code::KeyT)),
+            ChordMatch::NoMatch
+        );
+    }
+}
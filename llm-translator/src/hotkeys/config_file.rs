@@ -0,0 +1,253 @@
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use thiserror::Error;
+
+use super::validator::{HotkeyValidationError, HotkeyValidator, KeyCombo};
+
+const MODIFIER_NAMES: &[&str] = &[
+    "ctrl", "control", "alt", "option", "shift", "cmd", "command", "meta", "super", "win", "windows",
+];
+
+/// Why a single line of a hotkey config file failed to parse or register.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorReason {
+    /// The line isn't `binding = accelerator` at all, or the accelerator is
+    /// malformed (e.g. a trailing `+` with no key).
+    UnknownSymbol(String),
+    /// A segment before the final one isn't a recognized modifier name.
+    InvalidModifier(String),
+    /// The final segment isn't a recognized key name.
+    InvalidKey(String),
+    /// Conflicts with a hotkey the OS or desktop environment already owns.
+    SystemConflict,
+    /// Reserved for a common clipboard/undo operation.
+    Reserved,
+    /// Needs at least one modifier key.
+    TooSimple,
+    /// The same accelerator is already bound by an earlier line.
+    AlreadyRegistered,
+}
+
+impl fmt::Display for ParseErrorReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseErrorReason::UnknownSymbol(s) => write!(f, "unrecognized line: '{}'", s),
+            ParseErrorReason::InvalidModifier(s) => write!(f, "unknown modifier: '{}'", s),
+            ParseErrorReason::InvalidKey(s) => write!(f, "unknown key: '{}'", s),
+            ParseErrorReason::SystemConflict => write!(f, "conflicts with a system shortcut"),
+            ParseErrorReason::Reserved => write!(f, "reserved for a common clipboard/undo operation"),
+            ParseErrorReason::TooSimple => write!(f, "needs at least one modifier key"),
+            ParseErrorReason::AlreadyRegistered => write!(f, "already bound by an earlier line"),
+        }
+    }
+}
+
+/// A single parse/registration failure, 1-based to match what a user sees in
+/// an editor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub reason: ParseErrorReason,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.reason)
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum ConfigError {
+    #[error("Hotkey config file not found: {0}")]
+    ConfigNotFound(String),
+
+    #[error("Failed to read hotkey config file: {0}")]
+    Io(String),
+
+    #[error("Invalid hotkey config ({} error(s))", .0.len())]
+    InvalidConfig(Vec<ParseError>),
+}
+
+/// Load hotkey bindings from a file, one binding per line (`translate =
+/// Ctrl+Shift+T`). Blank lines and lines starting with `#` are ignored. The
+/// whole file is parsed before returning - every bad line is collected into
+/// `ConfigError::InvalidConfig` rather than bailing on the first one, so the
+/// UI can point at exactly which lines failed and why.
+pub fn load_hotkey_config_file(path: &Path) -> Result<HotkeyValidator, ConfigError> {
+    let contents = fs::read_to_string(path).map_err(|e| {
+        if e.kind() == std::io::ErrorKind::NotFound {
+            ConfigError::ConfigNotFound(path.display().to_string())
+        } else {
+            ConfigError::Io(e.to_string())
+        }
+    })?;
+
+    parse_hotkey_config(&contents)
+}
+
+/// Parse hotkey config file contents directly, e.g. for loading from an
+/// embedded default or a string already read by the caller.
+pub fn parse_hotkey_config(contents: &str) -> Result<HotkeyValidator, ConfigError> {
+    let mut validator = HotkeyValidator::new();
+    let mut errors = Vec::new();
+
+    for (i, raw_line) in contents.lines().enumerate() {
+        let line_number = i + 1;
+        let line = raw_line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let Some((_label, spec)) = line.split_once('=') else {
+            errors.push(ParseError {
+                line: line_number,
+                reason: ParseErrorReason::UnknownSymbol(line.to_string()),
+            });
+            continue;
+        };
+        let spec = spec.trim();
+
+        if let Some(reason) = invalid_modifier_segment(spec) {
+            errors.push(ParseError { line: line_number, reason });
+            continue;
+        }
+
+        let combo = match KeyCombo::from_string(spec) {
+            Ok(combo) => combo,
+            Err(HotkeyValidationError::UnknownKey(key)) => {
+                errors.push(ParseError {
+                    line: line_number,
+                    reason: ParseErrorReason::InvalidKey(key),
+                });
+                continue;
+            }
+            Err(_) => {
+                errors.push(ParseError {
+                    line: line_number,
+                    reason: ParseErrorReason::UnknownSymbol(spec.to_string()),
+                });
+                continue;
+            }
+        };
+
+        if let Err(e) = validator.register(combo) {
+            let reason = match e {
+                HotkeyValidationError::SystemConflict(_) => ParseErrorReason::SystemConflict,
+                HotkeyValidationError::AlreadyRegistered(_) => ParseErrorReason::AlreadyRegistered,
+                HotkeyValidationError::TooSimple(_) => ParseErrorReason::TooSimple,
+                HotkeyValidationError::Reserved(_) => ParseErrorReason::Reserved,
+                _ => ParseErrorReason::UnknownSymbol(spec.to_string()),
+            };
+            errors.push(ParseError { line: line_number, reason });
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(validator)
+    } else {
+        Err(ConfigError::InvalidConfig(errors))
+    }
+}
+
+/// Check every modifier segment (all but the last `+`-separated part) for an
+/// unrecognized name, e.g. `Shiftt` in `Ctrl+Shiftt+T`.
+fn invalid_modifier_segment(spec: &str) -> Option<ParseErrorReason> {
+    let parts: Vec<&str> = spec.split('+').collect();
+    if parts.len() < 2 {
+        return None;
+    }
+
+    parts[..parts.len() - 1]
+        .iter()
+        .find(|part| !MODIFIER_NAMES.contains(&part.to_lowercase().as_str()))
+        .map(|part| ParseErrorReason::InvalidModifier(part.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_hotkey_config_accepts_valid_bindings() {
+        let contents = "translate = Ctrl+Shift+T\ncancel = Ctrl+Shift+X\n";
+        assert!(parse_hotkey_config(contents).is_ok());
+    }
+
+    #[test]
+    fn test_parse_hotkey_config_ignores_blank_and_comment_lines() {
+        let contents = "# a comment\n\ntranslate = Ctrl+Shift+T\n";
+        assert!(parse_hotkey_config(contents).is_ok());
+    }
+
+    #[test]
+    fn test_parse_hotkey_config_reports_unknown_symbol_with_line_number() {
+        let contents = "translate = Ctrl+Shift+T\nno equals sign here\n";
+        match parse_hotkey_config(contents) {
+            Err(ConfigError::InvalidConfig(errors)) => {
+                assert_eq!(errors.len(), 1);
+                assert_eq!(errors[0].line, 2);
+                assert!(matches!(errors[0].reason, ParseErrorReason::UnknownSymbol(_)));
+            }
+            other => panic!("expected InvalidConfig, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_hotkey_config_reports_invalid_modifier() {
+        let contents = "translate = Ctrl+Shiftt+T\n";
+        match parse_hotkey_config(contents) {
+            Err(ConfigError::InvalidConfig(errors)) => {
+                assert_eq!(errors[0].line, 1);
+                assert_eq!(
+                    errors[0].reason,
+                    ParseErrorReason::InvalidModifier("Shiftt".to_string())
+                );
+            }
+            other => panic!("expected InvalidConfig, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_hotkey_config_reports_invalid_key() {
+        let contents = "translate = Ctrl+Shift+Zzz\n";
+        match parse_hotkey_config(contents) {
+            Err(ConfigError::InvalidConfig(errors)) => {
+                assert_eq!(
+                    errors[0].reason,
+                    ParseErrorReason::InvalidKey("Zzz".to_string())
+                );
+            }
+            other => panic!("expected InvalidConfig, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_hotkey_config_reports_duplicate_binding() {
+        let contents = "translate = Ctrl+Shift+T\ncancel = Ctrl+Shift+T\n";
+        match parse_hotkey_config(contents) {
+            Err(ConfigError::InvalidConfig(errors)) => {
+                assert_eq!(errors.len(), 1);
+                assert_eq!(errors[0].line, 2);
+                assert_eq!(errors[0].reason, ParseErrorReason::AlreadyRegistered);
+            }
+            other => panic!("expected InvalidConfig, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_hotkey_config_collects_every_bad_line() {
+        let contents = "translate = Ctrl+Shiftt+T\ncancel = Ctrl+Shift+Zzz\n";
+        match parse_hotkey_config(contents) {
+            Err(ConfigError::InvalidConfig(errors)) => assert_eq!(errors.len(), 2),
+            other => panic!("expected InvalidConfig, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_load_hotkey_config_file_reports_not_found() {
+        let result = load_hotkey_config_file(Path::new("/nonexistent/hotkeys.conf"));
+        assert!(matches!(result, Err(ConfigError::ConfigNotFound(_))));
+    }
+}
@@ -1,17 +1,21 @@
 use tokio::sync::mpsc;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::RwLock;
+use tokio_util::sync::CancellationToken;
 use tracing::{info, error, debug};
 
 use super::listener::HotkeyEvent;
 use crate::clipboard::{ClipboardManager, SelectionError};
-use crate::validation::{TextValidator, TextValidationError};
+use crate::validation::{TextValidator, TextValidationError, ModelKind, TextChunk};
 use crate::config::Config;
+use crate::llm::{build_provider, ChatCompletionRequest, ChatMessage, CompletionProvider, LlmError};
 
 #[derive(Debug, Clone)]
 pub enum TranslationEvent {
     TranslateRequest(String),
     CancelTranslation,
+    TranslationCanceled,
     ValidationError(String),
     ClipboardError(String),
 }
@@ -21,6 +25,10 @@ pub struct HotkeyHandler {
     event_sender: mpsc::Sender<TranslationEvent>,
     clipboard_manager: Arc<RwLock<ClipboardManager>>,
     text_validator: Arc<TextValidator>,
+    provider: Arc<dyn CompletionProvider>,
+    /// Abort signal for whichever translation is currently in flight via
+    /// `translate_directly`, tripped by `handle_cancel`.
+    active_cancellation: Arc<RwLock<Option<CancellationToken>>>,
 }
 
 impl HotkeyHandler {
@@ -31,9 +39,10 @@ impl HotkeyHandler {
         let config_lock = config.blocking_read();
         
         // Create clipboard manager
-        let clipboard_manager = ClipboardManager::new(
+        let clipboard_manager = ClipboardManager::with_settings(
             config_lock.behavior.preserve_clipboard,
             config_lock.limits.clipboard_timeout_ms,
+            &config_lock.clipboard,
         )?;
         
         // Create text validator
@@ -44,32 +53,86 @@ impl HotkeyHandler {
         )
         .with_whitespace_allowed(config_lock.validation.allow_only_whitespace)
         .with_binary_detection(config_lock.validation.detect_binary_data)
-        .with_trim(config_lock.validation.trim_before_validate);
-        
+        .with_trim(config_lock.validation.trim_before_validate)
+        .with_tokenizer(ModelKind::from_model_name(&config_lock.api.model));
+
+        let provider: Arc<dyn CompletionProvider> = Arc::from(build_provider(
+            &config_lock.api,
+            config_lock.api.api_key.clone().unwrap_or_default(),
+        )?);
+
         drop(config_lock);
-        
+
         Ok(Self {
             config,
             event_sender,
             clipboard_manager: Arc::new(RwLock::new(clipboard_manager)),
             text_validator: Arc::new(text_validator),
+            provider,
+            active_cancellation: Arc::new(RwLock::new(None)),
         })
     }
 
-    /// Handle incoming hotkey events
-    pub async fn handle_event(&self, event: HotkeyEvent) {
+    /// Translate text directly through the configured provider, bypassing the
+    /// queued `TranslationManager` path. Useful for one-off calls such as the
+    /// embedded HTTP server or quick previews. The request can be interrupted
+    /// by `handle_cancel` while it's in flight.
+    pub async fn translate_directly(
+        &self,
+        text: &str,
+        system_prompt: &str,
+    ) -> Result<String, LlmError> {
+        let config = self.config.read().await;
+        let request = ChatCompletionRequest::new(&config.api.model)
+            .with_message(ChatMessage::system(system_prompt))
+            .with_message(ChatMessage::user(text))
+            .with_temperature(config.api.temperature);
+        drop(config);
+
+        let token = CancellationToken::new();
+        *self.active_cancellation.write().await = Some(token.clone());
+
+        let result = self.provider.send(request, Some(token)).await;
+
+        *self.active_cancellation.write().await = None;
+
+        match result {
+            Ok(response) => Ok(response.get_content().unwrap_or_default().to_string()),
+            Err(LlmError::Cancelled) => {
+                self.send_event(TranslationEvent::TranslationCanceled).await;
+                Err(LlmError::Cancelled)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Handle an incoming hotkey event, returning whether it was handled.
+    /// The caller reading from `HotkeyListener`'s dispatch channel is
+    /// expected to forward this back through the paired oneshot sender, so
+    /// the listener knows whether to fall through to the next-highest-
+    /// priority binding for that key.
+    pub async fn handle_event(&self, event: HotkeyEvent) -> bool {
         match event {
             HotkeyEvent::Translate => {
                 info!("Translation hotkey pressed");
                 self.handle_translate().await;
+                true
             }
             HotkeyEvent::Cancel => {
                 info!("Cancel hotkey pressed");
                 self.handle_cancel().await;
+                true
             }
             HotkeyEvent::Custom(name) => {
                 debug!("Custom hotkey pressed: {}", name);
                 // Handle custom hotkeys if needed
+                false
+            }
+            HotkeyEvent::EnterMode(mode) => {
+                debug!("Entered hotkey mode: {}", mode);
+                // The listener itself tracks the active mode; handlers have
+                // nothing to do beyond observing the switch.
+                true
             }
         }
     }
@@ -135,8 +198,13 @@ impl HotkeyHandler {
         }
     }
 
-    /// Handle cancel request
+    /// Handle cancel request: trip the abort signal for any in-flight direct
+    /// translation, then notify listeners the cancel hotkey was pressed.
     async fn handle_cancel(&self) {
+        if let Some(token) = self.active_cancellation.read().await.clone() {
+            token.cancel();
+        }
+
         self.send_event(TranslationEvent::CancelTranslation).await;
     }
 
@@ -156,7 +224,25 @@ impl HotkeyHandler {
     /// Replace selected text with translation
     pub async fn replace_selection(&self, translated_text: &str) -> Result<(), SelectionError> {
         let mut clipboard = self.clipboard_manager.write().await;
-        clipboard.replace_selection(translated_text).await
+        clipboard.replace_selection(translated_text).await?;
+        drop(clipboard);
+
+        // If the original clipboard content was restored, the translated
+        // text is already gone from the clipboard - nothing left to wipe.
+        let behavior = self.config.read().await.behavior.clone();
+        if !behavior.preserve_clipboard && behavior.clipboard_autoclear_seconds > 0 {
+            if let Err(e) = ClipboardManager::set_text_ephemeral(
+                &self.clipboard_manager,
+                translated_text,
+                Duration::from_secs(behavior.clipboard_autoclear_seconds),
+            )
+            .await
+            {
+                error!("Failed to schedule clipboard auto-clear: {}", e);
+            }
+        }
+
+        Ok(())
     }
 
     /// Update configuration
@@ -164,9 +250,10 @@ impl HotkeyHandler {
         *self.config.write().await = new_config.clone();
         
         // Recreate components with new config
-        if let Ok(new_clipboard) = ClipboardManager::new(
+        if let Ok(new_clipboard) = ClipboardManager::with_settings(
             new_config.behavior.preserve_clipboard,
             new_config.limits.clipboard_timeout_ms,
+            &new_config.clipboard,
         ) {
             *self.clipboard_manager.write().await = new_clipboard;
         }
@@ -180,19 +267,26 @@ impl HotkeyHandler {
             .with_whitespace_allowed(new_config.validation.allow_only_whitespace)
             .with_binary_detection(new_config.validation.detect_binary_data)
             .with_trim(new_config.validation.trim_before_validate)
+            .with_tokenizer(ModelKind::from_model_name(&new_config.api.model))
         );
-        
+
+        if let Ok(provider) = build_provider(&new_config.api, new_config.api.api_key.clone().unwrap_or_default()) {
+            self.provider = Arc::from(provider);
+        }
+
         info!("Hotkey handler configuration updated");
     }
 
-    /// Check if text should be auto-split
+    /// Check if text should be auto-split, measured in real tokens rather
+    /// than byte length so the decision lines up with the model's context window.
     pub async fn should_auto_split(&self, text: &str) -> bool {
         let config = self.config.read().await;
-        config.behavior.auto_split_long_text && text.len() > config.limits.max_text_length
+        config.behavior.auto_split_long_text
+            && self.text_validator.count_tokens(text) > config.limits.max_tokens_estimate
     }
 
-    /// Split text into chunks
-    pub fn split_text(&self, text: &str) -> Vec<String> {
+    /// Split text into token-budgeted chunks for piecewise translation
+    pub fn split_text(&self, text: &str) -> Vec<TextChunk> {
         self.text_validator.split_text(text)
     }
 }
@@ -233,6 +327,18 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_handle_cancel_trips_active_token() {
+        let (handler, _) = create_test_handler().await;
+
+        let token = CancellationToken::new();
+        *handler.active_cancellation.write().await = Some(token.clone());
+
+        handler.handle_cancel().await;
+
+        assert!(token.is_cancelled());
+    }
+
     #[tokio::test]
     async fn test_handle_cancel_event() {
         let (handler, mut receiver) = create_test_handler().await;
@@ -249,10 +355,11 @@ mod tests {
         
         let long_text = "a".repeat(10000);
         let chunks = handler.split_text(&long_text);
-        
+        let max_tokens = handler.config.read().await.limits.max_tokens_estimate;
+
         assert!(chunks.len() > 1);
         for chunk in chunks {
-            assert!(chunk.len() <= 5000); // Default max length
+            assert!(chunk.token_count <= max_tokens);
         }
     }
 
@@ -1,26 +1,43 @@
 use global_hotkey::{GlobalHotKeyManager, GlobalHotKeyEvent, HotKeyState};
 use global_hotkey::hotkey::{HotKey, Code, Modifiers};
 use std::sync::Arc;
-use tokio::sync::{mpsc, RwLock};
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot, RwLock};
 use std::collections::HashMap;
 use tracing::{debug, info, warn, error};
 use thiserror::Error;
 
-use super::validator::{HotkeyValidator, KeyCombo, ValidationResult};
+use super::chord::{ChordMatch, ChordMatcher, ChordTrie, KeyChord};
+use super::validator::{HotkeyValidator, KeyCombo, ValidationResult, DEFAULT_MODE};
 
 #[derive(Error, Debug)]
 pub enum HotkeyError {
     #[error("Failed to register hotkey: {0}")]
     RegistrationError(String),
-    
+
     #[error("Hotkey conflict detected")]
     ConflictError,
-    
+
     #[error("Invalid hotkey format: {0}")]
     InvalidFormat(String),
-    
+
     #[error("Hotkey manager error: {0}")]
     ManagerError(String),
+
+    #[error("{0}")]
+    Unsupported(String),
+}
+
+/// The `global-hotkey` crate's backend is X11-specific and segfaults if a
+/// manager is constructed under Wayland, so this is checked before touching
+/// it at all rather than relying on it to fail gracefully.
+fn is_wayland_session() -> bool {
+    std::env::var("XDG_SESSION_TYPE")
+        .map(|session_type| session_type.eq_ignore_ascii_case("wayland"))
+        .unwrap_or(false)
+        || std::env::var("WAYLAND_DISPLAY")
+            .map(|display| !display.is_empty())
+            .unwrap_or(false)
 }
 
 #[derive(Debug, Clone)]
@@ -28,21 +45,135 @@ pub enum HotkeyEvent {
     Translate,
     Cancel,
     Custom(String),
+    /// Switches the listener's active mode, so subsequently pressed combos
+    /// dispatch only the bindings registered in that mode.
+    EnterMode(String),
+}
+
+/// Whether a binding fires on key-down or key-up. Defaults to
+/// `KeyPressed`, matching the behavior every binding had before this
+/// existed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Trigger {
+    KeyPressed,
+    KeyReleased,
+}
+
+impl Default for Trigger {
+    fn default() -> Self {
+        Trigger::KeyPressed
+    }
+}
+
+fn trigger_matches(trigger: Trigger, state: HotKeyState) -> bool {
+    matches!(
+        (trigger, state),
+        (Trigger::KeyPressed, HotKeyState::Pressed) | (Trigger::KeyReleased, HotKeyState::Released)
+    )
+}
+
+/// Outcome of a [`HotkeyListener::begin_capture`] session, reported back
+/// through the channel it returns.
+#[derive(Debug, Clone)]
+pub enum CaptureResult {
+    /// The combo that was physically pressed, in the host platform's
+    /// display form (`KeyCombo`'s `Display`) - feed it to
+    /// [`HotkeyListener::check_conflict`] before registering it.
+    Captured(String),
+    /// Escape was pressed, which during a capture means "clear the
+    /// binding" rather than a combo to assign.
+    Unset,
+}
+
+/// How long the dispatch chain waits for a handler's "was this handled"
+/// reply before treating the binding as unhandled and falling through to
+/// the next-highest-priority binding sharing the same key id.
+const DISPATCH_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Send each event in `chain` (already priority-ordered, highest first) in
+/// turn, stopping as soon as a handler replies that it handled the event.
+/// A dropped reply channel or a reply that takes longer than
+/// [`DISPATCH_TIMEOUT`] is treated the same as an explicit "not handled".
+async fn dispatch_chain(
+    sender: mpsc::Sender<(HotkeyEvent, oneshot::Sender<bool>)>,
+    chain: Vec<HotkeyEvent>,
+) {
+    for hotkey_event in chain {
+        let (reply_tx, reply_rx) = oneshot::channel();
+
+        debug!("Hotkey dispatched: {:?}", hotkey_event);
+        if let Err(e) = sender.send((hotkey_event, reply_tx)).await {
+            error!("Failed to send hotkey event: {}", e);
+            return;
+        }
+
+        match tokio::time::timeout(DISPATCH_TIMEOUT, reply_rx).await {
+            Ok(Ok(true)) => return,
+            Ok(Ok(false)) => continue,
+            Ok(Err(_)) => continue,
+            Err(_) => {
+                warn!("Hotkey handler timed out after {:?} - falling through", DISPATCH_TIMEOUT);
+                continue;
+            }
+        }
+    }
 }
 
 pub struct HotkeyListener {
-    manager: GlobalHotKeyManager,
+    /// `None` when the session was detected as Wayland, where the
+    /// `global-hotkey` backend can't be safely constructed. Every method
+    /// that would touch the manager short-circuits to
+    /// [`HotkeyError::Unsupported`] in that case (`start_listening` just
+    /// returns without spawning); methods that only consult the validator,
+    /// the mode state or the sequence trie run as usual.
+    manager: Option<GlobalHotKeyManager>,
     validator: Arc<RwLock<HotkeyValidator>>,
-    registered_hotkeys: Arc<RwLock<HashMap<u32, (HotKey, HotkeyEvent)>>>,
-    event_sender: mpsc::Sender<HotkeyEvent>,
+    /// An OS-level accelerator can only ever be grabbed once, so the same
+    /// physical combo shared across modes is registered with the manager a
+    /// single time and keeps one `(mode, event, trigger, priority)` entry
+    /// per mode that binds it; the receiver loop picks the entries matching
+    /// the active mode and trigger, trying them highest-priority first.
+    registered_hotkeys: Arc<RwLock<HashMap<u32, (HotKey, Vec<(String, HotkeyEvent, Trigger, u32)>)>>>,
+    /// Each dispatched event carries a oneshot reply channel the handler
+    /// uses to signal whether it handled the event, so the listener knows
+    /// whether to fall through to the next-highest-priority binding.
+    event_sender: mpsc::Sender<(HotkeyEvent, oneshot::Sender<bool>)>,
     fallback_hotkeys: Vec<KeyCombo>,
+    current_mode: Arc<RwLock<String>>,
+    /// Prefix trie of registered multi-key sequences (e.g. `Ctrl+K Ctrl+T`),
+    /// walked one combo at a time by `chord_matcher` as presses arrive.
+    sequence_trie: Arc<RwLock<ChordTrie>>,
+    /// Maps a sequence's action id (the trie leaf set by `register_sequence`)
+    /// to the event it dispatches once the full sequence resolves.
+    sequence_actions: Arc<RwLock<HashMap<String, HotkeyEvent>>>,
+    /// Every OS-level accelerator id that belongs to at least one
+    /// registered sequence step, so the receiver loop can tell a sequence
+    /// press apart from an id with no binding at all.
+    sequence_combo_ids: Arc<RwLock<HashMap<u32, KeyCombo>>>,
+    chord_matcher: Arc<RwLock<ChordMatcher>>,
+    /// Set by `begin_capture` while waiting for the user to press a
+    /// replacement shortcut from a settings UI; cleared automatically once
+    /// a capture resolves, or by `cancel_capture`.
+    capture_sender: Arc<RwLock<Option<mpsc::Sender<CaptureResult>>>>,
+    /// OS-level accelerators grabbed only for the duration of a capture, so
+    /// the receiver loop can recognize one without confusing it for a real
+    /// binding, and torn down as soon as the capture resolves or is
+    /// cancelled.
+    capture_combo_ids: Arc<RwLock<HashMap<u32, KeyCombo>>>,
 }
 
 impl HotkeyListener {
-    pub fn new(event_sender: mpsc::Sender<HotkeyEvent>) -> Result<Self, HotkeyError> {
-        let manager = GlobalHotKeyManager::new()
-            .map_err(|e| HotkeyError::ManagerError(e.to_string()))?;
-        
+    pub fn new(event_sender: mpsc::Sender<(HotkeyEvent, oneshot::Sender<bool>)>) -> Result<Self, HotkeyError> {
+        let manager = if is_wayland_session() {
+            warn!("Wayland session detected - global hotkeys are unavailable, disabling listener");
+            None
+        } else {
+            Some(
+                GlobalHotKeyManager::new()
+                    .map_err(|e| HotkeyError::ManagerError(e.to_string()))?,
+            )
+        };
+
         Ok(Self {
             manager,
             validator: Arc::new(RwLock::new(HotkeyValidator::new())),
@@ -53,28 +184,74 @@ impl HotkeyListener {
                 KeyCombo::new(Modifiers::CONTROL | Modifiers::ALT, Code::KeyT),
                 KeyCombo::new(Modifiers::ALT | Modifiers::SHIFT, Code::KeyT),
             ],
+            current_mode: Arc::new(RwLock::new(DEFAULT_MODE.to_string())),
+            sequence_trie: Arc::new(RwLock::new(ChordTrie::new())),
+            sequence_actions: Arc::new(RwLock::new(HashMap::new())),
+            sequence_combo_ids: Arc::new(RwLock::new(HashMap::new())),
+            chord_matcher: Arc::new(RwLock::new(ChordMatcher::new())),
+            capture_sender: Arc::new(RwLock::new(None)),
+            capture_combo_ids: Arc::new(RwLock::new(HashMap::new())),
+        })
+    }
+
+    /// The manager, or an [`HotkeyError::Unsupported`] telling callers to
+    /// fall back to the tray menu when running under Wayland.
+    fn require_manager(&self) -> Result<&GlobalHotKeyManager, HotkeyError> {
+        self.manager.as_ref().ok_or_else(|| {
+            HotkeyError::Unsupported(
+                "global hotkeys unavailable on Wayland - use the tray menu instead".to_string(),
+            )
         })
     }
 
-    /// Register a hotkey with automatic conflict resolution
+    /// Register a hotkey with automatic conflict resolution, bound in the
+    /// default mode, firing on key-down at the default priority.
     pub async fn register_hotkey(
         &self,
         combo_str: &str,
         event: HotkeyEvent,
         use_fallback: bool,
+    ) -> Result<HotKey, HotkeyError> {
+        self.register_hotkey_in_mode(
+            combo_str,
+            event,
+            DEFAULT_MODE,
+            Trigger::default(),
+            0,
+            use_fallback,
+        )
+        .await
+    }
+
+    /// Same as [`HotkeyListener::register_hotkey`], but binds the combo in
+    /// `mode` rather than the default mode - the binding only dispatches,
+    /// and only conflicts with other bindings, while `mode` is active -
+    /// fires on `trigger` (press or release), and competes at `priority`
+    /// against any other binding sharing the same key id: when more than
+    /// one binding matches a press, the listener tries them
+    /// highest-priority first and falls through if a handler reports it
+    /// didn't handle the event.
+    pub async fn register_hotkey_in_mode(
+        &self,
+        combo_str: &str,
+        event: HotkeyEvent,
+        mode: &str,
+        trigger: Trigger,
+        priority: u32,
+        use_fallback: bool,
     ) -> Result<HotKey, HotkeyError> {
         let combo = KeyCombo::from_string(combo_str)
             .map_err(|e| HotkeyError::InvalidFormat(e.to_string()))?;
-        
+
         // Try to register the requested hotkey
-        match self.try_register(&combo, event.clone()).await {
+        match self.try_register(&combo, event.clone(), mode, trigger, priority).await {
             Ok(hotkey) => {
                 info!("Successfully registered hotkey: {}", combo_str);
                 Ok(hotkey)
             }
             Err(e) if use_fallback => {
                 warn!("Failed to register {}: {}. Trying fallbacks...", combo_str, e);
-                self.register_with_fallback(event).await
+                self.register_with_fallback(event, mode, trigger, priority).await
             }
             Err(e) => Err(e),
         }
@@ -85,16 +262,21 @@ impl HotkeyListener {
         &self,
         combo: &KeyCombo,
         event: HotkeyEvent,
+        mode: &str,
+        trigger: Trigger,
+        priority: u32,
     ) -> Result<HotKey, HotkeyError> {
+        let manager = self.require_manager()?;
+
         // Create the hotkey first
         let hotkey = HotKey::new(Some(combo.modifiers), combo.key);
-        
+
         // Acquire both locks to ensure atomicity
         let mut validator = self.validator.write().await;
         let mut registered = self.registered_hotkeys.write().await;
-        
+
         // Validate the hotkey while holding the lock
-        match validator.validate(combo) {
+        match validator.validate_in_mode(combo, mode) {
             ValidationResult::Valid => {},
             ValidationResult::SystemConflict => {
                 return Err(HotkeyError::ConflictError);
@@ -109,24 +291,35 @@ impl HotkeyListener {
                 return Err(HotkeyError::InvalidFormat("This key combination is reserved".to_string()));
             }
         }
-        
-        // Try to register with the system
-        if let Err(e) = self.manager.register(hotkey) {
-            return Err(HotkeyError::RegistrationError(e.to_string()));
+
+        // Only grab the accelerator from the OS the first time any mode
+        // binds it - a second mode sharing the same physical combo just
+        // adds another dispatch entry alongside the existing registration.
+        let already_registered_at_os_level = registered.contains_key(&hotkey.id());
+        if !already_registered_at_os_level {
+            if let Err(e) = manager.register(hotkey) {
+                return Err(HotkeyError::RegistrationError(e.to_string()));
+            }
         }
-        
+
         // Update validator state - this should not fail since we already validated
-        if let Err(_) = validator.register(combo.clone()) {
-            // Rollback system registration
-            let _ = self.manager.unregister(hotkey);
+        if let Err(_) = validator.register_in_mode(combo.clone(), mode) {
+            // Rollback system registration, but only if we were the one who just grabbed it
+            if !already_registered_at_os_level {
+                let _ = manager.unregister(hotkey);
+            }
             return Err(HotkeyError::ConflictError);
         }
-        
+
         // Update registered hotkeys
-        registered.insert(hotkey.id(), (hotkey, event));
-        
-        debug!("Registered hotkey: {:?} with id: {}", combo, hotkey.id());
-        
+        registered
+            .entry(hotkey.id())
+            .or_insert_with(|| (hotkey, Vec::new()))
+            .1
+            .push((mode.to_string(), event, trigger, priority));
+
+        debug!("Registered hotkey: {:?} with id: {} in mode '{}'", combo, hotkey.id(), mode);
+
         Ok(hotkey)
     }
 
@@ -134,9 +327,12 @@ impl HotkeyListener {
     async fn register_with_fallback(
         &self,
         event: HotkeyEvent,
+        mode: &str,
+        trigger: Trigger,
+        priority: u32,
     ) -> Result<HotKey, HotkeyError> {
         for fallback in &self.fallback_hotkeys {
-            match self.try_register(fallback, event.clone()).await {
+            match self.try_register(fallback, event.clone(), mode, trigger, priority).await {
                 Ok(hotkey) => {
                     info!("Registered fallback hotkey: {}", fallback.to_string());
                     return Ok(hotkey);
@@ -147,36 +343,188 @@ impl HotkeyListener {
                 }
             }
         }
-        
+
         Err(HotkeyError::ConflictError)
     }
 
-    /// Unregister a hotkey
+    /// Layer another handler onto a combo already bound in `mode`, competing
+    /// at `priority` instead of conflicting with the existing binding -
+    /// unlike [`HotkeyListener::register_hotkey_in_mode`], an existing
+    /// binding for the same (combo, mode) is expected here, since the whole
+    /// point is chaining several priorities onto one physical key (e.g. a
+    /// context-sensitive "cancel" binding layered over a global "translate"
+    /// default). The first binding for a combo still goes through full
+    /// validation so system conflicts, reserved combos etc. are still
+    /// caught.
+    pub async fn register_priority_binding_in_mode(
+        &self,
+        combo_str: &str,
+        event: HotkeyEvent,
+        mode: &str,
+        trigger: Trigger,
+        priority: u32,
+    ) -> Result<HotKey, HotkeyError> {
+        let combo = KeyCombo::from_string(combo_str)
+            .map_err(|e| HotkeyError::InvalidFormat(e.to_string()))?;
+        self.require_manager()?;
+        let hotkey = HotKey::new(Some(combo.modifiers), combo.key);
+
+        let already_registered_at_os_level = {
+            let registered = self.registered_hotkeys.read().await;
+            registered.contains_key(&hotkey.id())
+        };
+
+        if !already_registered_at_os_level {
+            return self.try_register(&combo, event, mode, trigger, priority).await;
+        }
+
+        let mut registered = self.registered_hotkeys.write().await;
+        let bindings = &mut registered.get_mut(&hotkey.id()).expect("checked above").1;
+        if bindings.iter().any(|(m, _, _, p)| m == mode && *p == priority) {
+            return Err(HotkeyError::ConflictError);
+        }
+        bindings.push((mode.to_string(), event, trigger, priority));
+
+        debug!(
+            "Layered priority {} binding onto hotkey id {} in mode '{}'",
+            priority,
+            hotkey.id(),
+            mode
+        );
+        Ok(hotkey)
+    }
+
+    /// Register a multi-key sequence such as `Ctrl+K Ctrl+T`, dispatching
+    /// `event` once every step has been pressed in order within the
+    /// matcher's timeout. A step whose accelerator is already grabbed by an
+    /// earlier sequence (or another step of this one) is not re-registered
+    /// with the OS - the trie path is what distinguishes sequences, not the
+    /// individual accelerator.
+    pub async fn register_sequence(
+        &self,
+        combo_strs: &[&str],
+        event: HotkeyEvent,
+    ) -> Result<(), HotkeyError> {
+        let manager = self.require_manager()?;
+
+        let combos = combo_strs
+            .iter()
+            .map(|s| {
+                KeyCombo::from_string(s).map_err(|e| HotkeyError::InvalidFormat(e.to_string()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let chord = KeyChord(combos.clone());
+
+        let validator = self.validator.read().await;
+        for combo in &combos {
+            match validator.validate(combo) {
+                ValidationResult::Valid | ValidationResult::AlreadyRegistered => {}
+                ValidationResult::SystemConflict => return Err(HotkeyError::ConflictError),
+                ValidationResult::TooSimple => {
+                    return Err(HotkeyError::InvalidFormat(
+                        "Hotkey needs at least one modifier".to_string(),
+                    ))
+                }
+                ValidationResult::Reserved => {
+                    return Err(HotkeyError::InvalidFormat(
+                        "This key combination is reserved".to_string(),
+                    ))
+                }
+            }
+        }
+        drop(validator);
+
+        let action_id = format!("sequence:{}", chord);
+
+        let mut trie = self.sequence_trie.write().await;
+        trie.register(&chord, action_id.clone())
+            .map_err(|e| HotkeyError::InvalidFormat(e.to_string()))?;
+        drop(trie);
+
+        let mut combo_ids = self.sequence_combo_ids.write().await;
+        for combo in &combos {
+            let hotkey = HotKey::new(Some(combo.modifiers), combo.key);
+            if !combo_ids.contains_key(&hotkey.id()) {
+                if let Err(e) = manager.register(hotkey) {
+                    return Err(HotkeyError::RegistrationError(e.to_string()));
+                }
+                combo_ids.insert(hotkey.id(), combo.clone());
+            }
+        }
+        drop(combo_ids);
+
+        self.sequence_actions.write().await.insert(action_id, event);
+
+        info!("Registered hotkey sequence: {}", chord);
+        Ok(())
+    }
+
+    /// Check whether every step of a would-be sequence is free to register,
+    /// returning the first non-`Valid` result encountered.
+    pub async fn check_sequence_conflict(
+        &self,
+        combo_strs: &[&str],
+    ) -> Result<ValidationResult, HotkeyError> {
+        let validator = self.validator.read().await;
+        for combo_str in combo_strs {
+            let combo = KeyCombo::from_string(combo_str)
+                .map_err(|e| HotkeyError::InvalidFormat(e.to_string()))?;
+            match validator.validate(&combo) {
+                ValidationResult::Valid | ValidationResult::AlreadyRegistered => {}
+                other => return Ok(other),
+            }
+        }
+        Ok(ValidationResult::Valid)
+    }
+
+    /// Switch the active mode - bindings registered in other modes stop
+    /// dispatching until their mode is active again.
+    pub async fn enter_mode(&self, mode: impl Into<String>) {
+        let mut current = self.current_mode.write().await;
+        *current = mode.into();
+    }
+
+    /// Return to the default mode.
+    pub async fn escape_mode(&self) {
+        let mut current = self.current_mode.write().await;
+        *current = DEFAULT_MODE.to_string();
+    }
+
+    /// The currently active mode.
+    pub async fn current_mode(&self) -> String {
+        self.current_mode.read().await.clone()
+    }
+
+    /// Unregister a hotkey, dropping every mode's binding to it.
     pub async fn unregister_hotkey(&self, hotkey: &HotKey) -> Result<(), HotkeyError> {
+        let manager = self.require_manager()?;
+
         // Acquire locks first for atomicity
         let mut validator = self.validator.write().await;
         let mut registered = self.registered_hotkeys.write().await;
-        
+
         // Get the combo info before removing
-        let combo = if let Some((hk, _)) = registered.get(&hotkey.id()) {
-            KeyCombo::new(
-                hk.mods.unwrap_or(Modifiers::empty()),
-                hk.key
+        let (combo, modes) = if let Some((hk, bindings)) = registered.get(&hotkey.id()) {
+            (
+                KeyCombo::new(hk.mods.unwrap_or(Modifiers::empty()), hk.key),
+                bindings.iter().map(|(mode, ..)| mode.clone()).collect::<Vec<_>>(),
             )
         } else {
             return Ok(()); // Already unregistered
         };
-        
+
         // Unregister from system
-        self.manager.unregister(hotkey)
+        manager.unregister(hotkey)
             .map_err(|e| HotkeyError::ManagerError(e.to_string()))?;
-        
+
         // Remove from our records
         registered.remove(&hotkey.id());
-        validator.unregister(&combo);
-        
+        for mode in modes {
+            validator.unregister_in_mode(&combo, &mode);
+        }
+
         info!("Unregistered hotkey with id: {}", hotkey.id());
-        
+
         Ok(())
     }
 
@@ -185,48 +533,196 @@ impl HotkeyListener {
         let registered = self.registered_hotkeys.read().await;
         let hotkeys: Vec<HotKey> = registered.values().map(|(hk, _)| *hk).collect();
         drop(registered);
-        
+
         for hotkey in hotkeys {
             self.unregister_hotkey(&hotkey).await?;
         }
-        
+
         let mut validator = self.validator.write().await;
         validator.clear_registered();
-        
+
+        Ok(())
+    }
+
+    /// Enter interactive rebinding mode: grab a small set of candidate
+    /// accelerators purely to observe the next one physically pressed, and
+    /// report it through the returned channel instead of dispatching it as
+    /// a normal [`HotkeyEvent`]. Nothing is permanently bound - the caller
+    /// still has to run the result through [`HotkeyListener::check_conflict`]
+    /// and call [`HotkeyListener::register_hotkey_in_mode`] itself.
+    ///
+    /// `global-hotkey` only ever reports presses for accelerators already
+    /// registered with the OS, so there's no way to observe a truly
+    /// arbitrary keypress - this sweeps the same candidate combos offered by
+    /// [`HotkeyListener::get_suggestions`] plus the configured fallbacks, so
+    /// a press outside that set simply isn't seen. Pressing Escape resolves
+    /// to [`CaptureResult::Unset`], which a settings UI can use to clear an
+    /// existing binding.
+    pub async fn begin_capture(&self) -> Result<mpsc::Receiver<CaptureResult>, HotkeyError> {
+        let manager = self.require_manager()?;
+        let (tx, rx) = mpsc::channel(1);
+
+        let mut candidates = self.fallback_hotkeys.clone();
+        candidates.extend(
+            self.validator
+                .read()
+                .await
+                .suggest_alternatives(&KeyCombo::new(Modifiers::ALT, Code::Tab)),
+        );
+        candidates.push(KeyCombo::new(Modifiers::empty(), Code::Escape));
+
+        let mut combo_ids = self.capture_combo_ids.write().await;
+        // Tear down any leftover registrations from a capture that was
+        // never cancelled or resolved, before grabbing this round's set.
+        for (_, combo) in combo_ids.drain() {
+            let _ = manager.unregister(HotKey::new(Some(combo.modifiers), combo.key));
+        }
+        for combo in candidates {
+            let hotkey = HotKey::new(Some(combo.modifiers), combo.key);
+            if combo_ids.contains_key(&hotkey.id()) {
+                continue;
+            }
+            // Best-effort: a candidate already grabbed as a real binding
+            // just won't be capturable this round.
+            if manager.register(hotkey).is_ok() {
+                combo_ids.insert(hotkey.id(), combo);
+            }
+        }
+        drop(combo_ids);
+
+        *self.capture_sender.write().await = Some(tx);
+        Ok(rx)
+    }
+
+    /// Leave capture mode without resolving it, tearing down the transient
+    /// OS-level registrations [`HotkeyListener::begin_capture`] made.
+    pub async fn cancel_capture(&self) -> Result<(), HotkeyError> {
+        let manager = self.require_manager()?;
+        *self.capture_sender.write().await = None;
+
+        let mut combo_ids = self.capture_combo_ids.write().await;
+        for (_, combo) in combo_ids.drain() {
+            let _ = manager.unregister(HotKey::new(Some(combo.modifiers), combo.key));
+        }
+
         Ok(())
     }
 
     /// Start listening for hotkey events
     pub async fn start_listening(self: Arc<Self>) {
+        if self.manager.is_none() {
+            warn!("No global hotkey manager (Wayland session) - listener thread not started");
+            return;
+        }
+
         let receiver = GlobalHotKeyEvent::receiver();
         let registered = self.registered_hotkeys.clone();
         let sender = self.event_sender.clone();
-        
+        let current_mode = self.current_mode.clone();
+        let sequence_trie = self.sequence_trie.clone();
+        let sequence_actions = self.sequence_actions.clone();
+        let sequence_combo_ids = self.sequence_combo_ids.clone();
+        let chord_matcher = self.chord_matcher.clone();
+        let capture_sender = self.capture_sender.clone();
+        let capture_combo_ids = self.capture_combo_ids.clone();
+        let listener_for_capture = self.clone();
+
         // Use blocking thread for the receiver since it's a blocking operation
         std::thread::spawn(move || {
             info!("Hotkey listener started");
-            
+
             loop {
                 // This blocks until an event is available, avoiding busy-wait
                 match receiver.recv() {
                     Ok(event) => {
-                        if event.state == HotKeyState::Pressed {
-                            // Use blocking read since we're in a sync context
-                            let registered_lock = registered.blocking_read();
-                            
-                            if let Some((_, hotkey_event)) = registered_lock.get(&event.id) {
-                                debug!("Hotkey pressed: {:?}", hotkey_event);
-                                
-                                let event_clone = hotkey_event.clone();
+                        let capturing = capture_sender.blocking_read().clone();
+                        if let Some(capture_tx) = capturing {
+                            if event.state != HotKeyState::Pressed {
+                                continue;
+                            }
+                            let combo_ids_lock = capture_combo_ids.blocking_read();
+                            let combo = combo_ids_lock.get(&event.id).cloned();
+                            drop(combo_ids_lock);
+
+                            if let Some(combo) = combo {
+                                let result = if combo.key == Code::Escape && combo.modifiers.is_empty() {
+                                    CaptureResult::Unset
+                                } else {
+                                    CaptureResult::Captured(combo.to_string())
+                                };
+
+                                let listener_for_teardown = listener_for_capture.clone();
+                                tokio::spawn(async move {
+                                    let _ = capture_tx.send(result).await;
+                                    let _ = listener_for_teardown.cancel_capture().await;
+                                });
+                            }
+                            continue;
+                        }
+
+                        // Use blocking read since we're in a sync context
+                        let registered_lock = registered.blocking_read();
+
+                        if let Some((_, bindings)) = registered_lock.get(&event.id) {
+                            let active_mode = current_mode.blocking_read();
+                            let mut matching: Vec<(u32, HotkeyEvent)> = bindings
+                                .iter()
+                                .filter(|(mode, _, trigger, _)| {
+                                    *mode == *active_mode && trigger_matches(*trigger, event.state)
+                                })
+                                .map(|(_, hotkey_event, _, priority)| (*priority, hotkey_event.clone()))
+                                .collect();
+                            drop(active_mode);
+                            drop(registered_lock);
+
+                            if matching.is_empty() {
+                                debug!("No binding for hotkey id {} in the active mode/trigger", event.id);
+                            } else {
+                                // Highest priority first, so the dispatch
+                                // chain tries the most specific binding
+                                // before falling through to the default.
+                                matching.sort_by(|a, b| b.0.cmp(&a.0));
+                                let chain: Vec<HotkeyEvent> = matching.into_iter().map(|(_, e)| e).collect();
+
+                                debug!("Dispatching hotkey id {} through {} candidate binding(s)", event.id, chain.len());
                                 let sender_clone = sender.clone();
-                                
-                                // Send event asynchronously
                                 tokio::spawn(async move {
-                                    if let Err(e) = sender_clone.send(event_clone).await {
-                                        error!("Failed to send hotkey event: {}", e);
-                                    }
+                                    dispatch_chain(sender_clone, chain).await;
                                 });
                             }
+                        } else {
+                            drop(registered_lock);
+
+                            // Not a modal single-key binding - check whether
+                            // it's a step of a registered chord sequence.
+                            if event.state == HotKeyState::Pressed {
+                                let combo_ids_lock = sequence_combo_ids.blocking_read();
+                                let combo = combo_ids_lock.get(&event.id).cloned();
+                                drop(combo_ids_lock);
+
+                                if let Some(combo) = combo {
+                                    let trie_lock = sequence_trie.blocking_read();
+                                    let mut matcher_lock = chord_matcher.blocking_write();
+                                    let chord_match = matcher_lock.feed(&trie_lock, combo);
+                                    drop(matcher_lock);
+                                    drop(trie_lock);
+
+                                    if let ChordMatch::Matched(action_id) = chord_match {
+                                        let actions_lock = sequence_actions.blocking_read();
+                                        let hotkey_event = actions_lock.get(&action_id).cloned();
+                                        drop(actions_lock);
+
+                                        if let Some(hotkey_event) = hotkey_event {
+                                            debug!("Hotkey sequence matched: {:?}", hotkey_event);
+
+                                            let sender_clone = sender.clone();
+                                            tokio::spawn(async move {
+                                                dispatch_chain(sender_clone, vec![hotkey_event]).await;
+                                            });
+                                        }
+                                    }
+                                }
+                            }
                         }
                     }
                     Err(e) => {
@@ -238,17 +734,20 @@ impl HotkeyListener {
         });
     }
 
-    /// Get currently registered hotkeys
+    /// Get currently registered hotkeys, across every mode.
     pub async fn get_registered(&self) -> Vec<(String, HotkeyEvent)> {
         let registered = self.registered_hotkeys.read().await;
-        
+
         registered.values()
-            .map(|(hotkey, event)| {
+            .flat_map(|(hotkey, bindings)| {
                 let combo = KeyCombo::new(
                     hotkey.mods.unwrap_or(Modifiers::empty()),
                     hotkey.key
                 );
-                (combo.to_string(), event.clone())
+                bindings
+                    .iter()
+                    .map(move |(_, event, ..)| (combo.to_string(), event.clone()))
+                    .collect::<Vec<_>>()
             })
             .collect()
     }
@@ -303,6 +802,289 @@ mod tests {
         assert!(matches!(result, Ok(ValidationResult::Valid)));
     }
 
+    #[tokio::test]
+    async fn test_register_hotkey_returns_unsupported_on_wayland() {
+        std::env::set_var("XDG_SESSION_TYPE", "wayland");
+        let (sender, _receiver) = mpsc::channel(10);
+        let listener = HotkeyListener::new(sender).unwrap();
+
+        let result = listener
+            .register_hotkey("Ctrl+Shift+T", HotkeyEvent::Translate, false)
+            .await;
+        assert!(matches!(result, Err(HotkeyError::Unsupported(_))));
+
+        std::env::remove_var("XDG_SESSION_TYPE");
+    }
+
+    #[tokio::test]
+    async fn test_start_listening_is_a_no_op_on_wayland() {
+        std::env::set_var("XDG_SESSION_TYPE", "wayland");
+        let (sender, _receiver) = mpsc::channel(10);
+        let listener = Arc::new(HotkeyListener::new(sender).unwrap());
+        assert!(listener.manager.is_none());
+
+        // Should return immediately rather than spawning the blocking
+        // receiver thread.
+        listener.start_listening().await;
+
+        std::env::remove_var("XDG_SESSION_TYPE");
+    }
+
+    #[tokio::test]
+    async fn test_enter_mode_and_escape_mode_roundtrip() {
+        let (sender, _receiver) = mpsc::channel(10);
+        let listener = HotkeyListener::new(sender).unwrap();
+
+        assert_eq!(listener.current_mode().await, "normal");
+        listener.enter_mode("editing").await;
+        assert_eq!(listener.current_mode().await, "editing");
+        listener.escape_mode().await;
+        assert_eq!(listener.current_mode().await, "normal");
+    }
+
+    #[tokio::test]
+    async fn test_register_hotkey_in_mode_allows_same_combo_in_different_mode() {
+        let (sender, _receiver) = mpsc::channel(10);
+        let listener = HotkeyListener::new(sender).unwrap();
+
+        let first = listener
+            .register_hotkey_in_mode(
+                "Ctrl+Shift+T",
+                HotkeyEvent::Translate,
+                "normal",
+                Trigger::default(),
+                0,
+                false,
+            )
+            .await;
+        assert!(first.is_ok());
+
+        // The same physical combo bound in a different mode shouldn't
+        // conflict, since only one mode is ever active at a time.
+        let second = listener
+            .register_hotkey_in_mode(
+                "Ctrl+Shift+T",
+                HotkeyEvent::Custom("editing-action".to_string()),
+                "editing",
+                Trigger::default(),
+                0,
+                false,
+            )
+            .await;
+        assert!(second.is_ok());
+
+        // Both bindings show up in get_registered regardless of active mode.
+        let registered = listener.get_registered().await;
+        assert_eq!(registered.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_register_hotkey_in_mode_rejects_duplicate_in_same_mode() {
+        let (sender, _receiver) = mpsc::channel(10);
+        let listener = HotkeyListener::new(sender).unwrap();
+
+        let first = listener
+            .register_hotkey_in_mode(
+                "Ctrl+Shift+T",
+                HotkeyEvent::Translate,
+                "normal",
+                Trigger::default(),
+                0,
+                false,
+            )
+            .await;
+        assert!(first.is_ok());
+
+        let second = listener
+            .register_hotkey_in_mode(
+                "Ctrl+Shift+T",
+                HotkeyEvent::Cancel,
+                "normal",
+                Trigger::default(),
+                0,
+                false,
+            )
+            .await;
+        assert!(matches!(second, Err(HotkeyError::ConflictError)));
+    }
+
+    #[test]
+    fn test_trigger_matches_press_and_release() {
+        assert!(trigger_matches(Trigger::KeyPressed, HotKeyState::Pressed));
+        assert!(!trigger_matches(Trigger::KeyPressed, HotKeyState::Released));
+        assert!(trigger_matches(Trigger::KeyReleased, HotKeyState::Released));
+        assert!(!trigger_matches(Trigger::KeyReleased, HotKeyState::Pressed));
+    }
+
+    #[tokio::test]
+    async fn test_register_priority_binding_in_mode_layers_without_conflict() {
+        let (sender, _receiver) = mpsc::channel(10);
+        let listener = HotkeyListener::new(sender).unwrap();
+
+        let first = listener
+            .register_priority_binding_in_mode(
+                "Ctrl+Shift+T",
+                HotkeyEvent::Cancel,
+                "normal",
+                Trigger::default(),
+                10,
+            )
+            .await;
+        assert!(first.is_ok());
+
+        // Layering a second, lower-priority handler onto the same combo and
+        // mode doesn't conflict - that's the whole point of priority chains.
+        let second = listener
+            .register_priority_binding_in_mode(
+                "Ctrl+Shift+T",
+                HotkeyEvent::Translate,
+                "normal",
+                Trigger::default(),
+                0,
+            )
+            .await;
+        assert!(second.is_ok());
+
+        let registered = listener.get_registered().await;
+        assert_eq!(registered.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_register_priority_binding_in_mode_rejects_same_priority_twice() {
+        let (sender, _receiver) = mpsc::channel(10);
+        let listener = HotkeyListener::new(sender).unwrap();
+
+        listener
+            .register_priority_binding_in_mode(
+                "Ctrl+Shift+T",
+                HotkeyEvent::Cancel,
+                "normal",
+                Trigger::default(),
+                10,
+            )
+            .await
+            .unwrap();
+
+        let second = listener
+            .register_priority_binding_in_mode(
+                "Ctrl+Shift+T",
+                HotkeyEvent::Translate,
+                "normal",
+                Trigger::default(),
+                10,
+            )
+            .await;
+        assert!(matches!(second, Err(HotkeyError::ConflictError)));
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_chain_falls_through_to_next_priority() {
+        let (sender, mut receiver) = mpsc::channel(10);
+
+        let chain = vec![HotkeyEvent::Cancel, HotkeyEvent::Translate];
+        let dispatch = tokio::spawn(dispatch_chain(sender, chain));
+
+        // The highest-priority candidate declines to handle it.
+        let (event, reply) = receiver.recv().await.unwrap();
+        assert!(matches!(event, HotkeyEvent::Cancel));
+        reply.send(false).unwrap();
+
+        // Falls through to the next-highest-priority candidate.
+        let (event, reply) = receiver.recv().await.unwrap();
+        assert!(matches!(event, HotkeyEvent::Translate));
+        reply.send(true).unwrap();
+
+        dispatch.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_register_sequence_adds_chord_to_trie() {
+        let (sender, _receiver) = mpsc::channel(10);
+        let listener = HotkeyListener::new(sender).unwrap();
+
+        let result = listener
+            .register_sequence(&["Ctrl+K", "Ctrl+T"], HotkeyEvent::Translate)
+            .await;
+        assert!(result.is_ok());
+
+        let trie = listener.sequence_trie.read().await;
+        let chord = KeyChord::from_string("Ctrl+K Ctrl+T").unwrap();
+        // Re-registering the exact same sequence is rejected as a duplicate
+        // binding, confirming it's already present in the trie.
+        drop(trie);
+        let mut trie = listener.sequence_trie.write().await;
+        assert!(trie.register(&chord, "other").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_check_sequence_conflict_reports_first_bad_step() {
+        let (sender, _receiver) = mpsc::channel(10);
+        let listener = HotkeyListener::new(sender).unwrap();
+
+        let result = listener
+            .check_sequence_conflict(&["T", "Ctrl+K"])
+            .await
+            .unwrap();
+        assert_eq!(result, ValidationResult::TooSimple);
+
+        let result = listener
+            .check_sequence_conflict(&["Ctrl+K", "Ctrl+T"])
+            .await
+            .unwrap();
+        assert_eq!(result, ValidationResult::Valid);
+    }
+
+    #[tokio::test]
+    async fn test_chord_matcher_resolves_registered_sequence() {
+        let (sender, _receiver) = mpsc::channel(10);
+        let listener = HotkeyListener::new(sender).unwrap();
+
+        listener
+            .register_sequence(&["Ctrl+K", "Ctrl+T"], HotkeyEvent::Translate)
+            .await
+            .unwrap();
+
+        let trie = listener.sequence_trie.read().await;
+        let mut matcher = listener.chord_matcher.write().await;
+
+        let first = matcher.feed(
+            &trie,
+            KeyCombo::new(Modifiers::CONTROL, Code::KeyK),
+        );
+        assert_eq!(first, ChordMatch::Pending);
+
+        let second = matcher.feed(
+            &trie,
+            KeyCombo::new(Modifiers::CONTROL, Code::KeyT),
+        );
+        assert!(matches!(second, ChordMatch::Matched(_)));
+    }
+
+    #[tokio::test]
+    async fn test_begin_capture_returns_unsupported_on_wayland() {
+        std::env::set_var("XDG_SESSION_TYPE", "wayland");
+        let (sender, _receiver) = mpsc::channel(10);
+        let listener = HotkeyListener::new(sender).unwrap();
+
+        let result = listener.begin_capture().await;
+        assert!(matches!(result, Err(HotkeyError::Unsupported(_))));
+
+        std::env::remove_var("XDG_SESSION_TYPE");
+    }
+
+    #[tokio::test]
+    async fn test_cancel_capture_clears_capture_sender() {
+        let (sender, _receiver) = mpsc::channel(10);
+        let listener = HotkeyListener::new(sender).unwrap();
+
+        let _rx = listener.begin_capture().await.unwrap();
+        assert!(listener.capture_sender.read().await.is_some());
+
+        listener.cancel_capture().await.unwrap();
+        assert!(listener.capture_sender.read().await.is_none());
+        assert!(listener.capture_combo_ids.read().await.is_empty());
+    }
+
     #[tokio::test]
     async fn test_get_suggestions() {
         let (sender, _receiver) = mpsc::channel(10);
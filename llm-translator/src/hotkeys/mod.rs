@@ -1,7 +1,16 @@
 pub mod validator;
+pub mod chord;
+pub mod config_file;
 pub mod listener;
 pub mod handlers;
 
-pub use validator::{HotkeyValidator, KeyCombo, ValidationResult, HotkeyValidationError};
-pub use listener::HotkeyListener;
+pub use validator::{
+    HotkeyValidator, KeyCombo, ValidationResult, HotkeyValidationError, validate_hotkey_settings,
+    DEFAULT_MODE,
+};
+pub use chord::{KeyChord, ChordTrie, ChordMatcher, ChordMatch};
+pub use config_file::{
+    load_hotkey_config_file, parse_hotkey_config, ConfigError, ParseError, ParseErrorReason,
+};
+pub use listener::{HotkeyListener, Trigger, CaptureResult};
 pub use handlers::HotkeyHandler;
\ No newline at end of file
@@ -1,8 +1,11 @@
 use std::collections::HashMap;
 use global_hotkey::{hotkey::{HotKey, Code, Modifiers}, GlobalHotKeyEvent};
 use cfg_if::cfg_if;
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
 use thiserror::Error;
 
+use crate::config::HotkeySettings;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Platform {
     Windows,
@@ -24,7 +27,16 @@ impl Platform {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+/// Order Apple's Human Interface Guidelines use when stacking modifier
+/// glyphs: control, option, shift, command.
+const MAC_MODIFIER_GLYPHS: &[(char, Modifiers)] = &[
+    ('⌃', Modifiers::CONTROL),
+    ('⌥', Modifiers::ALT),
+    ('⇧', Modifiers::SHIFT),
+    ('⌘', Modifiers::META),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct KeyCombo {
     pub modifiers: Modifiers,
     pub key: Code,
@@ -35,7 +47,17 @@ impl KeyCombo {
         Self { modifiers, key }
     }
 
+    /// Parse either word form (`Ctrl+Shift+T`) or the glyph form produced by
+    /// [`KeyCombo::to_display_string`] on macOS (`⌃⌥⇧⌘T`, no separator).
     pub fn from_string(combo: &str) -> Result<Self, HotkeyValidationError> {
+        if combo.chars().any(|c| MAC_MODIFIER_GLYPHS.iter().any(|(g, _)| *g == c)) {
+            Self::from_glyph_string(combo)
+        } else {
+            Self::from_word_string(combo)
+        }
+    }
+
+    fn from_word_string(combo: &str) -> Result<Self, HotkeyValidationError> {
         // Parse strings like "Ctrl+Shift+T"
         let parts: Vec<&str> = combo.split('+').collect();
         if parts.is_empty() {
@@ -48,9 +70,9 @@ impl KeyCombo {
         for part in parts {
             match part.to_lowercase().as_str() {
                 "ctrl" | "control" => modifiers |= Modifiers::CONTROL,
-                "alt" => modifiers |= Modifiers::ALT,
+                "alt" | "option" => modifiers |= Modifiers::ALT,
                 "shift" => modifiers |= Modifiers::SHIFT,
-                "cmd" | "command" | "meta" | "win" | "windows" => modifiers |= Modifiers::META,
+                "cmd" | "command" | "meta" | "super" | "win" | "windows" => modifiers |= Modifiers::META,
                 _ => key_part = Some(part),
             }
         }
@@ -61,6 +83,29 @@ impl KeyCombo {
         Ok(Self { modifiers, key })
     }
 
+    fn from_glyph_string(combo: &str) -> Result<Self, HotkeyValidationError> {
+        let mut modifiers = Modifiers::empty();
+        let mut rest = combo;
+
+        loop {
+            let Some((glyph, modifier)) = MAC_MODIFIER_GLYPHS
+                .iter()
+                .find(|(glyph, _)| rest.starts_with(*glyph))
+            else {
+                break;
+            };
+            modifiers |= *modifier;
+            rest = &rest[glyph.len_utf8()..];
+        }
+
+        if rest.is_empty() {
+            return Err(HotkeyValidationError::InvalidFormat(combo.to_string()));
+        }
+
+        let key = Self::parse_key(rest)?;
+        Ok(Self { modifiers, key })
+    }
+
     fn parse_key(key: &str) -> Result<Code, HotkeyValidationError> {
         match key.to_uppercase().as_str() {
             "A" => Ok(Code::KeyA),
@@ -116,35 +161,101 @@ impl KeyCombo {
             "F10" => Ok(Code::F10),
             "F11" => Ok(Code::F11),
             "F12" => Ok(Code::F12),
+            "ARROWUP" | "UP" | "↑" => Ok(Code::ArrowUp),
+            "ARROWDOWN" | "DOWN" | "↓" => Ok(Code::ArrowDown),
+            "ARROWLEFT" | "LEFT" | "←" => Ok(Code::ArrowLeft),
+            "ARROWRIGHT" | "RIGHT" | "→" => Ok(Code::ArrowRight),
             _ => Err(HotkeyValidationError::UnknownKey(key.to_string())),
         }
     }
 
-    pub fn to_string(&self) -> String {
+    /// Render using the symbolic conventions `platform`'s users expect:
+    /// macOS concatenates modifier glyphs with no separator (`⌃⌥⇧⌘`),
+    /// Windows/Linux keep the `Ctrl+Alt+Shift+` word form. The result
+    /// round-trips back through [`KeyCombo::from_string`].
+    pub fn to_display_string(&self, platform: Platform) -> String {
+        match platform {
+            Platform::MacOS => self.to_glyph_string(),
+            Platform::Windows | Platform::Linux => self.to_word_string(),
+        }
+    }
+
+    fn to_glyph_string(&self) -> String {
+        let mut out = String::new();
+        if self.modifiers.contains(Modifiers::CONTROL) {
+            out.push('⌃');
+        }
+        if self.modifiers.contains(Modifiers::ALT) {
+            out.push('⌥');
+        }
+        if self.modifiers.contains(Modifiers::SHIFT) {
+            out.push('⇧');
+        }
+        if self.modifiers.contains(Modifiers::META) {
+            out.push('⌘');
+        }
+        out.push_str(&Self::key_display_name(self.key));
+        out
+    }
+
+    fn to_word_string(&self) -> String {
         let mut parts = Vec::new();
-        
+
         if self.modifiers.contains(Modifiers::CONTROL) {
-            parts.push("Ctrl");
+            parts.push("Ctrl".to_string());
         }
         if self.modifiers.contains(Modifiers::ALT) {
-            parts.push("Alt");
+            parts.push("Alt".to_string());
         }
         if self.modifiers.contains(Modifiers::SHIFT) {
-            parts.push("Shift");
+            parts.push("Shift".to_string());
         }
         if self.modifiers.contains(Modifiers::META) {
-            cfg_if! {
-                if #[cfg(target_os = "macos")] {
-                    parts.push("Cmd");
-                } else {
-                    parts.push("Win");
-                }
-            }
+            parts.push("Win".to_string());
         }
 
-        parts.push(&format!("{:?}", self.key).replace("Key", ""));
+        parts.push(Self::key_display_name(self.key));
         parts.join("+")
     }
+
+    /// Friendly name for a key outside the plain letter/digit range, e.g.
+    /// `Escape` -> `Esc`, arrows -> `↑ ↓ ← →`.
+    fn key_display_name(key: Code) -> String {
+        match key {
+            Code::Escape => "Esc".to_string(),
+            Code::Enter => "Enter".to_string(),
+            Code::Space => "Space".to_string(),
+            Code::Tab => "Tab".to_string(),
+            Code::Delete => "Delete".to_string(),
+            Code::ArrowUp => "↑".to_string(),
+            Code::ArrowDown => "↓".to_string(),
+            Code::ArrowLeft => "←".to_string(),
+            Code::ArrowRight => "→".to_string(),
+            _ => format!("{:?}", key).replace("Key", "").replace("Digit", ""),
+        }
+    }
+}
+
+impl std::fmt::Display for KeyCombo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_display_string(Platform::current()))
+    }
+}
+
+/// Serializes to the canonical `"Ctrl+Shift+T"` word form regardless of the
+/// host platform, so a config file stays portable and round-trips via
+/// [`KeyCombo::from_string`] on any machine that loads it.
+impl Serialize for KeyCombo {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_display_string(Platform::Windows))
+    }
+}
+
+impl<'de> Deserialize<'de> for KeyCombo {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let combo = String::deserialize(deserializer)?;
+        KeyCombo::from_string(&combo).map_err(D::Error::custom)
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -160,26 +271,65 @@ pub enum ValidationResult {
 pub enum HotkeyValidationError {
     #[error("Invalid hotkey format: {0}")]
     InvalidFormat(String),
-    
+
     #[error("Unknown key: {0}")]
     UnknownKey(String),
-    
-    #[error("Hotkey conflicts with system shortcut")]
-    SystemConflict,
-    
-    #[error("Hotkey is already registered")]
-    AlreadyRegistered,
-    
-    #[error("Hotkey is too simple (needs at least 2 keys)")]
-    TooSimple,
-    
-    #[error("Hotkey is reserved by the system")]
-    Reserved,
+
+    #[error("Hotkey '{0}' conflicts with system shortcut")]
+    SystemConflict(String),
+
+    #[error("Hotkey '{0}' is already registered")]
+    AlreadyRegistered(String),
+
+    #[error("Hotkey '{0}' is too simple (needs at least 2 keys)")]
+    TooSimple(String),
+
+    #[error("Hotkey '{0}' is reserved by the system")]
+    Reserved(String),
+
+    #[error("{0}")]
+    DuplicateBinding(String),
+}
+
+/// Parse every binding in `settings` (`translate`, `cancel`, `alternatives`)
+/// and fail on the first malformed accelerator, unknown key name, or chord
+/// bound to more than one action - e.g. the same combo appearing in both
+/// `translate` and `alternatives`. Called from `Config::validate()` so bad
+/// hotkeys are caught at load time instead of silently failing to register.
+pub fn validate_hotkey_settings(settings: &HotkeySettings) -> Result<(), HotkeyValidationError> {
+    let mut seen: Vec<(String, KeyCombo)> = Vec::new();
+
+    let mut check = |label: String, spec: &str| -> Result<(), HotkeyValidationError> {
+        let combo = KeyCombo::from_string(spec)?;
+        if let Some((other_label, _)) = seen.iter().find(|(_, c)| *c == combo) {
+            return Err(HotkeyValidationError::DuplicateBinding(format!(
+                "'{}' is bound to both '{}' and '{}'",
+                spec, other_label, label
+            )));
+        }
+        seen.push((label, combo));
+        Ok(())
+    };
+
+    check("translate".to_string(), &settings.translate)?;
+    check("cancel".to_string(), &settings.cancel)?;
+    for (i, alt) in settings.alternatives.iter().enumerate() {
+        check(format!("alternatives[{}]", i), alt)?;
+    }
+
+    Ok(())
 }
 
+/// The mode every binding lives in unless registered otherwise, e.g. via
+/// [`HotkeyValidator::register_in_mode`].
+pub const DEFAULT_MODE: &str = "normal";
+
 pub struct HotkeyValidator {
     known_system_hotkeys: HashMap<Platform, Vec<KeyCombo>>,
-    registered_hotkeys: Vec<KeyCombo>,
+    /// `(mode, combo)` pairs - the same combo can be registered in more than
+    /// one mode without conflicting, since only one mode is ever active at a
+    /// time.
+    registered_hotkeys: Vec<(String, KeyCombo)>,
 }
 
 impl HotkeyValidator {
@@ -228,6 +378,13 @@ impl HotkeyValidator {
     }
 
     pub fn validate(&self, combo: &KeyCombo) -> ValidationResult {
+        self.validate_in_mode(combo, DEFAULT_MODE)
+    }
+
+    /// Same as [`HotkeyValidator::validate`], but "already registered" only
+    /// considers bindings registered in `mode` - a combo bound in one mode
+    /// doesn't conflict with the same combo bound in another.
+    pub fn validate_in_mode(&self, combo: &KeyCombo, mode: &str) -> ValidationResult {
         let platform = Platform::current();
 
         // Check if it's too simple (need at least one modifier)
@@ -242,8 +399,8 @@ impl HotkeyValidator {
             }
         }
 
-        // Check if already registered
-        if self.registered_hotkeys.contains(combo) {
+        // Check if already registered in this mode
+        if self.registered_hotkeys.iter().any(|(m, c)| m == mode && c == combo) {
             return ValidationResult::AlreadyRegistered;
         }
 
@@ -265,20 +422,39 @@ impl HotkeyValidator {
     }
 
     pub fn register(&mut self, combo: KeyCombo) -> Result<(), HotkeyValidationError> {
-        match self.validate(&combo) {
+        self.register_in_mode(combo, DEFAULT_MODE)
+    }
+
+    /// Same as [`HotkeyValidator::register`], but records the binding under
+    /// `mode` so it only conflicts with other bindings in that same mode.
+    pub fn register_in_mode(&mut self, combo: KeyCombo, mode: &str) -> Result<(), HotkeyValidationError> {
+        match self.validate_in_mode(&combo, mode) {
             ValidationResult::Valid => {
-                self.registered_hotkeys.push(combo);
+                self.registered_hotkeys.push((mode.to_string(), combo));
                 Ok(())
             }
-            ValidationResult::SystemConflict => Err(HotkeyValidationError::SystemConflict),
-            ValidationResult::AlreadyRegistered => Err(HotkeyValidationError::AlreadyRegistered),
-            ValidationResult::TooSimple => Err(HotkeyValidationError::TooSimple),
-            ValidationResult::Reserved => Err(HotkeyValidationError::Reserved),
+            ValidationResult::SystemConflict => Err(HotkeyValidationError::SystemConflict(
+                combo.to_display_string(Platform::current()),
+            )),
+            ValidationResult::AlreadyRegistered => Err(HotkeyValidationError::AlreadyRegistered(
+                combo.to_display_string(Platform::current()),
+            )),
+            ValidationResult::TooSimple => Err(HotkeyValidationError::TooSimple(
+                combo.to_display_string(Platform::current()),
+            )),
+            ValidationResult::Reserved => Err(HotkeyValidationError::Reserved(
+                combo.to_display_string(Platform::current()),
+            )),
         }
     }
 
     pub fn unregister(&mut self, combo: &KeyCombo) {
-        self.registered_hotkeys.retain(|c| c != combo);
+        self.unregister_in_mode(combo, DEFAULT_MODE);
+    }
+
+    /// Same as [`HotkeyValidator::unregister`], scoped to `mode`.
+    pub fn unregister_in_mode(&mut self, combo: &KeyCombo, mode: &str) {
+        self.registered_hotkeys.retain(|(m, c)| !(m == mode && c == combo));
     }
 
     pub fn suggest_alternatives(&self, combo: &KeyCombo) -> Vec<KeyCombo> {
@@ -376,10 +552,41 @@ mod tests {
         // Second registration of same combo should fail
         assert!(matches!(
             validator.register(combo),
-            Err(HotkeyValidationError::AlreadyRegistered)
+            Err(HotkeyValidationError::AlreadyRegistered(_))
+        ));
+    }
+
+    #[test]
+    fn test_register_in_mode_allows_same_combo_in_different_modes() {
+        let mut validator = HotkeyValidator::new();
+        let combo = KeyCombo::new(Modifiers::CONTROL | Modifiers::SHIFT, Code::KeyT);
+
+        assert!(validator.register_in_mode(combo.clone(), "normal").is_ok());
+        assert!(validator.register_in_mode(combo.clone(), "editing").is_ok());
+
+        // But registering the same combo twice in the same mode still conflicts.
+        assert!(matches!(
+            validator.register_in_mode(combo, "normal"),
+            Err(HotkeyValidationError::AlreadyRegistered(_))
         ));
     }
 
+    #[test]
+    fn test_unregister_in_mode_only_clears_that_mode() {
+        let mut validator = HotkeyValidator::new();
+        let combo = KeyCombo::new(Modifiers::CONTROL | Modifiers::SHIFT, Code::KeyT);
+
+        validator.register_in_mode(combo.clone(), "normal").unwrap();
+        validator.register_in_mode(combo.clone(), "editing").unwrap();
+        validator.unregister_in_mode(&combo, "normal");
+
+        assert_eq!(validator.validate_in_mode(&combo, "normal"), ValidationResult::Valid);
+        assert_eq!(
+            validator.validate_in_mode(&combo, "editing"),
+            ValidationResult::AlreadyRegistered
+        );
+    }
+
     #[test]
     fn test_suggest_alternatives() {
         let validator = HotkeyValidator::new();
@@ -402,4 +609,107 @@ mod tests {
         assert!(string.contains("Shift"));
         assert!(string.contains("T"));
     }
+
+    #[test]
+    fn test_to_display_string_mac_uses_glyphs_with_no_separator() {
+        let combo = KeyCombo::new(Modifiers::CONTROL | Modifiers::SHIFT, Code::KeyT);
+        assert_eq!(combo.to_display_string(Platform::MacOS), "⌃⇧T");
+    }
+
+    #[test]
+    fn test_to_display_string_windows_uses_word_form() {
+        let combo = KeyCombo::new(Modifiers::CONTROL | Modifiers::SHIFT, Code::KeyT);
+        assert_eq!(combo.to_display_string(Platform::Windows), "Ctrl+Shift+T");
+    }
+
+    #[test]
+    fn test_to_display_string_friendly_key_names() {
+        let combo = KeyCombo::new(Modifiers::CONTROL, Code::Escape);
+        assert_eq!(combo.to_display_string(Platform::Windows), "Ctrl+Esc");
+        assert_eq!(combo.to_display_string(Platform::MacOS), "⌃Esc");
+
+        let arrow = KeyCombo::new(Modifiers::ALT, Code::ArrowUp);
+        assert_eq!(arrow.to_display_string(Platform::Windows), "Alt+↑");
+    }
+
+    #[test]
+    fn test_display_string_round_trips_through_from_string() {
+        for platform in [Platform::Windows, Platform::Linux, Platform::MacOS] {
+            for combo in [
+                KeyCombo::new(Modifiers::CONTROL | Modifiers::SHIFT, Code::KeyT),
+                KeyCombo::new(Modifiers::ALT, Code::Escape),
+                KeyCombo::new(Modifiers::CONTROL | Modifiers::ALT | Modifiers::SHIFT, Code::ArrowLeft),
+            ] {
+                let rendered = combo.to_display_string(platform);
+                assert_eq!(KeyCombo::from_string(&rendered).unwrap(), combo);
+            }
+        }
+    }
+
+    #[test]
+    fn test_key_combo_serializes_to_canonical_word_form() {
+        let combo = KeyCombo::new(Modifiers::CONTROL | Modifiers::SHIFT, Code::KeyT);
+        let json = serde_json::to_string(&combo).unwrap();
+        assert_eq!(json, "\"Ctrl+Shift+T\"");
+    }
+
+    #[test]
+    fn test_key_combo_deserializes_via_from_string() {
+        let combo: KeyCombo = serde_json::from_str("\"Ctrl+Alt+T\"").unwrap();
+        assert_eq!(combo, KeyCombo::new(Modifiers::CONTROL | Modifiers::ALT, Code::KeyT));
+    }
+
+    #[test]
+    fn test_key_combo_deserialize_rejects_invalid_string() {
+        let result: Result<KeyCombo, _> = serde_json::from_str("\"NotAKey\"");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_cross_platform_modifier_aliases() {
+        assert_eq!(
+            KeyCombo::from_string("Super+T").unwrap().modifiers,
+            Modifiers::META
+        );
+        assert_eq!(
+            KeyCombo::from_string("Option+T").unwrap().modifiers,
+            Modifiers::ALT
+        );
+    }
+
+    #[test]
+    fn test_validate_hotkey_settings_accepts_distinct_bindings() {
+        let settings = HotkeySettings {
+            translate: "Ctrl+Shift+T".to_string(),
+            cancel: "Escape".to_string(),
+            alternatives: vec!["Ctrl+Alt+T".to_string()],
+        };
+
+        assert!(validate_hotkey_settings(&settings).is_ok());
+    }
+
+    #[test]
+    fn test_validate_hotkey_settings_rejects_malformed_accelerator() {
+        let settings = HotkeySettings {
+            translate: "Ctrl+Shift+".to_string(),
+            cancel: "Escape".to_string(),
+            alternatives: vec![],
+        };
+
+        assert!(validate_hotkey_settings(&settings).is_err());
+    }
+
+    #[test]
+    fn test_validate_hotkey_settings_rejects_duplicate_binding() {
+        let settings = HotkeySettings {
+            translate: "Ctrl+Shift+T".to_string(),
+            cancel: "Escape".to_string(),
+            alternatives: vec!["Ctrl+Shift+T".to_string()],
+        };
+
+        assert!(matches!(
+            validate_hotkey_settings(&settings),
+            Err(HotkeyValidationError::DuplicateBinding(_))
+        ));
+    }
 }
\ No newline at end of file
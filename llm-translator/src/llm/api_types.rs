@@ -1,8 +1,42 @@
+use base64::Engine;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::Path;
 
 // OpenAI Chat Completion API types
 
+/// A single piece of message content: either plain text or an image, sent
+/// as OpenAI's content-array form so vision models can mix both in one turn.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ContentPart {
+    Text { text: String },
+    ImageUrl { image_url: ImageUrlDetail },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImageUrlDetail {
+    pub url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+impl ContentPart {
+    pub fn text(text: impl Into<String>) -> Self {
+        ContentPart::Text { text: text.into() }
+    }
+
+    pub fn image_url(url: impl Into<String>) -> Self {
+        ContentPart::ImageUrl {
+            image_url: ImageUrlDetail { url: url.into(), detail: None },
+        }
+    }
+
+    pub fn is_image(&self) -> bool {
+        matches!(self, ContentPart::ImageUrl { .. })
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChatCompletionRequest {
     pub model: String,
@@ -29,34 +63,83 @@ pub struct ChatCompletionRequest {
 #[serde(tag = "role")]
 pub enum ChatMessage {
     #[serde(rename = "system")]
-    System { content: String },
+    System { content: Vec<ContentPart> },
     #[serde(rename = "user")]
-    User { content: String },
+    User { content: Vec<ContentPart> },
     #[serde(rename = "assistant")]
-    Assistant { content: String },
+    Assistant { content: Vec<ContentPart> },
 }
 
 impl ChatMessage {
     pub fn system(content: impl Into<String>) -> Self {
         ChatMessage::System {
-            content: content.into(),
+            content: vec![ContentPart::text(content)],
         }
     }
 
     pub fn user(content: impl Into<String>) -> Self {
         ChatMessage::User {
-            content: content.into(),
+            content: vec![ContentPart::text(content)],
         }
     }
 
     pub fn assistant(content: impl Into<String>) -> Self {
         ChatMessage::Assistant {
-            content: content.into(),
+            content: vec![ContentPart::text(content)],
         }
     }
+
+    /// Build a user message containing text plus a local image, base64-encoded
+    /// into a `data:` URL for vision models.
+    pub fn user_with_image(
+        text: impl Into<String>,
+        image_path: impl AsRef<Path>,
+    ) -> std::io::Result<Self> {
+        let bytes = std::fs::read(image_path.as_ref())?;
+        let mime = guess_image_mime(image_path.as_ref());
+        let encoded = base64::engine::general_purpose::STANDARD.encode(&bytes);
+        let data_url = format!("data:{};base64,{}", mime, encoded);
+
+        Ok(ChatMessage::User {
+            content: vec![ContentPart::text(text), ContentPart::image_url(data_url)],
+        })
+    }
+
+    fn content_parts(&self) -> &[ContentPart] {
+        match self {
+            ChatMessage::System { content } | ChatMessage::User { content } | ChatMessage::Assistant { content } => content,
+        }
+    }
+
+    /// Concatenate the text parts of this message, ignoring any images.
+    pub fn as_text(&self) -> String {
+        self.content_parts()
+            .iter()
+            .filter_map(|part| match part {
+                ContentPart::Text { text } => Some(text.as_str()),
+                ContentPart::ImageUrl { .. } => None,
+            })
+            .collect::<Vec<_>>()
+            .join("")
+    }
+
+    pub fn has_image(&self) -> bool {
+        self.content_parts().iter().any(ContentPart::is_image)
+    }
 }
 
-#[derive(Debug, Clone, Deserialize)]
+/// Guess a MIME type from a file extension. Defaults to `image/png` when unknown.
+fn guess_image_mime(path: &Path) -> &'static str {
+    match path.extension().and_then(|ext| ext.to_str()).map(|ext| ext.to_lowercase()) {
+        Some(ref ext) if ext == "jpg" || ext == "jpeg" => "image/jpeg",
+        Some(ref ext) if ext == "gif" => "image/gif",
+        Some(ref ext) if ext == "webp" => "image/webp",
+        Some(ref ext) if ext == "png" => "image/png",
+        _ => "image/png",
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChatCompletionResponse {
     pub id: String,
     pub object: String,
@@ -68,26 +151,68 @@ pub struct ChatCompletionResponse {
     pub system_fingerprint: Option<String>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChatChoice {
     pub index: u32,
     pub message: ChatMessageResponse,
     pub finish_reason: String,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChatMessageResponse {
     pub role: String,
     pub content: String,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Usage {
     pub prompt_tokens: u32,
     pub completion_tokens: u32,
     pub total_tokens: u32,
 }
 
+// Streaming (chat.completion.chunk) types
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatCompletionChunk {
+    pub id: String,
+    pub object: String,
+    pub created: i64,
+    pub model: String,
+    pub choices: Vec<ChatChunkChoice>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatChunkChoice {
+    pub index: u32,
+    pub delta: ChatChunkDelta,
+    #[serde(default)]
+    pub finish_reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ChatChunkDelta {
+    #[serde(default)]
+    pub role: Option<String>,
+    #[serde(default)]
+    pub content: Option<String>,
+}
+
+impl ChatCompletionChunk {
+    /// Extract the delta text for the first choice, if any
+    pub fn delta_text(&self) -> Option<&str> {
+        self.choices
+            .first()
+            .and_then(|choice| choice.delta.content.as_deref())
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.choices
+            .first()
+            .and_then(|choice| choice.finish_reason.as_deref())
+            .is_some()
+    }
+}
+
 // Error response
 #[derive(Debug, Clone, Deserialize)]
 pub struct ErrorResponse {
@@ -105,6 +230,9 @@ pub struct ErrorDetail {
     pub code: Option<String>,
 }
 
+/// Default `max_tokens` floor applied once a request carries an image part
+const DEFAULT_VISION_MAX_TOKENS: u32 = 1024;
+
 // Request builder
 impl ChatCompletionRequest {
     pub fn new(model: impl Into<String>) -> Self {
@@ -123,6 +251,10 @@ impl ChatCompletionRequest {
     }
 
     pub fn with_message(mut self, message: ChatMessage) -> Self {
+        // Vision requests need far more headroom than a text-only reply
+        if message.has_image() && self.max_tokens.unwrap_or(0) < DEFAULT_VISION_MAX_TOKENS {
+            self.max_tokens = Some(DEFAULT_VISION_MAX_TOKENS);
+        }
         self.messages.push(message);
         self
     }
@@ -151,6 +283,11 @@ impl ChatCompletionRequest {
         self.user = Some(user_id.into());
         self
     }
+
+    pub fn with_streaming(mut self, stream: bool) -> Self {
+        self.stream = Some(stream);
+        self
+    }
 }
 
 // Helper to extract content from response
@@ -216,6 +353,49 @@ mod tests {
         assert_eq!(response.usage.total_tokens, 15);
     }
 
+    #[test]
+    fn test_text_message_content_array() {
+        let message = ChatMessage::user("Hello world");
+        assert_eq!(message.as_text(), "Hello world");
+        assert!(!message.has_image());
+    }
+
+    #[test]
+    fn test_image_message_raises_max_tokens_default() {
+        let request = ChatCompletionRequest::new("gpt-4.1-nano").with_message(ChatMessage::User {
+            content: vec![ContentPart::text("describe this"), ContentPart::image_url("data:image/png;base64,AAAA")],
+        });
+
+        assert_eq!(request.max_tokens, Some(DEFAULT_VISION_MAX_TOKENS));
+    }
+
+    #[test]
+    fn test_mime_type_guessing() {
+        assert_eq!(guess_image_mime(Path::new("shot.jpg")), "image/jpeg");
+        assert_eq!(guess_image_mime(Path::new("shot.jpeg")), "image/jpeg");
+        assert_eq!(guess_image_mime(Path::new("shot.gif")), "image/gif");
+        assert_eq!(guess_image_mime(Path::new("shot.unknown")), "image/png");
+    }
+
+    #[test]
+    fn test_chunk_deserialization() {
+        let json = r#"{
+            "id": "chatcmpl-123",
+            "object": "chat.completion.chunk",
+            "created": 1677652288,
+            "model": "gpt-4.1-nano",
+            "choices": [{
+                "index": 0,
+                "delta": { "content": "Привет" },
+                "finish_reason": null
+            }]
+        }"#;
+
+        let chunk: ChatCompletionChunk = serde_json::from_str(json).unwrap();
+        assert_eq!(chunk.delta_text(), Some("Привет"));
+        assert!(!chunk.is_complete());
+    }
+
     #[test]
     fn test_error_deserialization() {
         let json = r#"{
@@ -1,12 +1,27 @@
+use futures_util::StreamExt;
 use reqwest::{Client, StatusCode};
-use std::time::Duration;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use thiserror::Error;
 use tokio::time::sleep;
 use tracing::{debug, error, info, warn};
 use tokio_util::sync::CancellationToken;
 
-use super::api_types::{ChatCompletionRequest, ChatCompletionResponse, ErrorResponse};
+use super::api_types::{ChatCompletionChunk, ChatCompletionRequest, ChatCompletionResponse, ErrorResponse};
 use crate::config::ApiSettings;
+use crate::validation::{FreezeGate, RateLimiter};
+
+/// Callback fed with text deltas as a streamed completion arrives
+pub trait ReplyHandler {
+    fn text(&mut self, delta: &str);
+}
+
+impl<F: FnMut(&str)> ReplyHandler for F {
+    fn text(&mut self, delta: &str) {
+        self(delta)
+    }
+}
 
 #[derive(Error, Debug)]
 pub enum LlmError {
@@ -42,6 +57,172 @@ pub enum LlmError {
     
     #[error("Deserialization error: {0}")]
     DeserializationError(String),
+
+    #[error("Circuit breaker open, retry after {retry_after:?}")]
+    CircuitOpen { retry_after: Duration },
+
+    #[error("Request queue is full ({pending} pending)")]
+    QueueFull { pending: usize },
+
+    #[error("Provider can't carry this content: {0}")]
+    UnsupportedContent(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CircuitState {
+    Closed,
+    Open,
+    HalfOpen,
+}
+
+#[derive(Debug)]
+struct CircuitBreakerState {
+    state: CircuitState,
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+}
+
+/// Failsafe/consecutive-failure circuit breaker guarding `send_request`: once
+/// `failure_threshold` failures in a row land, it opens and rejects requests
+/// without touching the network until `cooldown` elapses, then allows a
+/// single half-open probe through before fully closing again.
+#[derive(Debug)]
+struct CircuitBreaker {
+    state: Mutex<CircuitBreakerState>,
+    failure_threshold: u32,
+    cooldown: Duration,
+}
+
+impl CircuitBreaker {
+    fn new(failure_threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            state: Mutex::new(CircuitBreakerState {
+                state: CircuitState::Closed,
+                consecutive_failures: 0,
+                opened_at: None,
+            }),
+            failure_threshold,
+            cooldown,
+        }
+    }
+
+    /// Returns `Err(retry_after)` if the breaker is open (or half-open with
+    /// its probe already let through) and requests should be rejected
+    /// without hitting the network right now.
+    fn guard(&self) -> Result<(), Duration> {
+        let mut state = self.state.lock().unwrap();
+
+        if state.state == CircuitState::Closed {
+            return Ok(());
+        }
+
+        let elapsed = state.opened_at.map(|t| t.elapsed()).unwrap_or(self.cooldown);
+        if elapsed >= self.cooldown {
+            state.state = CircuitState::HalfOpen;
+            // Restart the clock so only one probe gets through per cooldown.
+            state.opened_at = Some(Instant::now());
+            Ok(())
+        } else {
+            Err(self.cooldown - elapsed)
+        }
+    }
+
+    fn record_success(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.state = CircuitState::Closed;
+        state.consecutive_failures = 0;
+        state.opened_at = None;
+    }
+
+    fn record_failure(&self) {
+        let mut state = self.state.lock().unwrap();
+
+        if state.state == CircuitState::HalfOpen {
+            // The probe failed: reopen and restart the cooldown.
+            state.state = CircuitState::Open;
+            state.opened_at = Some(Instant::now());
+            return;
+        }
+
+        state.consecutive_failures += 1;
+        if state.consecutive_failures >= self.failure_threshold {
+            state.state = CircuitState::Open;
+            state.opened_at = Some(Instant::now());
+        }
+    }
+}
+
+/// Shared token bucket bounding total retries in flight across every
+/// concurrent request, so a struggling API isn't hammered by independent
+/// exponential backoffs piling up at once.
+#[derive(Debug)]
+struct RetryTokenBucket {
+    tokens: AtomicUsize,
+    capacity: usize,
+    timeout_cost: usize,
+    throttle_cost: usize,
+    refund: usize,
+}
+
+impl RetryTokenBucket {
+    fn new(capacity: usize, timeout_cost: usize, throttle_cost: usize, refund: usize) -> Self {
+        Self {
+            tokens: AtomicUsize::new(capacity),
+            capacity,
+            timeout_cost,
+            throttle_cost,
+            refund,
+        }
+    }
+
+    fn cost_for(&self, error: &LlmError) -> usize {
+        match error {
+            LlmError::RateLimited { .. } => self.throttle_cost,
+            LlmError::RequestError(_) | LlmError::Timeout(_) | LlmError::ServiceUnavailable => {
+                self.timeout_cost
+            }
+            _ => 0,
+        }
+    }
+
+    /// Try to pay for a retry after `error`. Returns `false` if the bucket
+    /// doesn't hold enough tokens, in which case the caller must stop retrying.
+    fn try_acquire(&self, error: &LlmError) -> bool {
+        let cost = self.cost_for(error);
+        if cost == 0 {
+            return true;
+        }
+
+        loop {
+            let current = self.tokens.load(Ordering::Acquire);
+            if current < cost {
+                return false;
+            }
+
+            if self
+                .tokens
+                .compare_exchange(current, current - cost, Ordering::AcqRel, Ordering::Acquire)
+                .is_ok()
+            {
+                return true;
+            }
+        }
+    }
+
+    fn refund(&self) {
+        loop {
+            let current = self.tokens.load(Ordering::Acquire);
+            let next = (current + self.refund).min(self.capacity);
+
+            if self
+                .tokens
+                .compare_exchange(current, next, Ordering::AcqRel, Ordering::Acquire)
+                .is_ok()
+            {
+                return;
+            }
+        }
+    }
 }
 
 pub struct LlmClient {
@@ -50,6 +231,10 @@ pub struct LlmClient {
     endpoint: String,
     max_retries: u32,
     timeout: Duration,
+    circuit_breaker: CircuitBreaker,
+    retry_bucket: RetryTokenBucket,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    freeze: FreezeGate,
 }
 
 impl LlmClient {
@@ -57,45 +242,109 @@ impl LlmClient {
         let client = Client::builder()
             .timeout(Duration::from_secs(settings.timeout_seconds))
             .build()?;
-            
+
         Ok(Self {
             client,
             api_key,
             endpoint: settings.endpoint.clone(),
             max_retries: settings.max_retries,
             timeout: Duration::from_secs(settings.timeout_seconds),
+            circuit_breaker: CircuitBreaker::new(
+                settings.circuit_breaker_threshold,
+                Duration::from_secs(settings.circuit_breaker_cooldown_seconds),
+            ),
+            retry_bucket: RetryTokenBucket::new(
+                settings.retry_bucket_capacity as usize,
+                settings.retry_bucket_timeout_cost as usize,
+                settings.retry_bucket_throttle_cost as usize,
+                settings.retry_bucket_refund as usize,
+            ),
+            rate_limiter: None,
+            freeze: FreezeGate::new(),
         })
     }
-    
+
+    /// Share a `RateLimiter` with this client so every response can
+    /// reconcile our local window against the server's live rate-limit headers.
+    pub fn with_rate_limiter(mut self, rate_limiter: Arc<RateLimiter>) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// Share a `FreezeGate` so a 429's `Retry-After` here also pauses
+    /// whatever else holds the same gate (typically the `RateLimiter`).
+    pub fn with_freeze_gate(mut self, freeze: FreezeGate) -> Self {
+        self.freeze = freeze;
+        self
+    }
+
+    /// Failures that count against the circuit breaker: anything suggesting
+    /// the endpoint itself is unhealthy rather than the request being invalid.
+    fn is_breaker_failure(error: &LlmError) -> bool {
+        matches!(
+            error,
+            LlmError::RequestError(_)
+                | LlmError::ServiceUnavailable
+                | LlmError::Timeout(_)
+                | LlmError::RateLimited { .. }
+        )
+    }
+
     pub async fn chat_completion(
         &self,
         request: ChatCompletionRequest,
         cancellation_token: Option<CancellationToken>,
     ) -> Result<ChatCompletionResponse, LlmError> {
+        if let Err(retry_after) = self.circuit_breaker.guard() {
+            return Err(LlmError::CircuitOpen { retry_after });
+        }
+
         let mut retry_count = 0;
-        
+
         loop {
             if let Some(token) = &cancellation_token {
                 if token.is_cancelled() {
                     return Err(LlmError::Cancelled);
                 }
             }
-            
+
+            // Collapse every caller's backoff into one coordinated pause
+            // while a prior 429's Retry-After freeze is still in effect,
+            // instead of firing a request we already know will be throttled.
+            if let Some(remaining) = self.freeze.remaining() {
+                if let Some(token) = &cancellation_token {
+                    tokio::select! {
+                        _ = sleep(remaining) => {},
+                        _ = token.cancelled() => return Err(LlmError::Cancelled),
+                    }
+                } else {
+                    sleep(remaining).await;
+                }
+            }
+
             match self.send_request(&request).await {
-                Ok(response) => return Ok(response),
+                Ok(response) => {
+                    self.circuit_breaker.record_success();
+                    self.retry_bucket.refund();
+                    return Ok(response);
+                }
                 Err(err) => {
+                    if Self::is_breaker_failure(&err) {
+                        self.circuit_breaker.record_failure();
+                    }
+
                     if !self.should_retry(&err, retry_count) {
                         return Err(err);
                     }
-                    
+
                     retry_count += 1;
                     let delay = self.calculate_retry_delay(retry_count, &err);
-                    
+
                     warn!(
                         "Request failed (attempt {}/{}): {}. Retrying in {:?}",
                         retry_count, self.max_retries, err, delay
                     );
-                    
+
                     // Check cancellation before sleeping
                     if let Some(token) = &cancellation_token {
                         tokio::select! {
@@ -123,9 +372,13 @@ impl LlmClient {
             .json(request)
             .send()
             .await?;
-            
+
+        if let Some(rate_limiter) = &self.rate_limiter {
+            rate_limiter.sync_from_headers(response.headers());
+        }
+
         let status = response.status();
-        
+
         if status.is_success() {
             let body = response.text().await?;
             serde_json::from_str::<ChatCompletionResponse>(&body)
@@ -174,7 +427,14 @@ impl LlmClient {
                     .and_then(|v| v.to_str().ok())
                     .and_then(|s| s.parse::<u64>().ok())
                     .map(Duration::from_secs);
-                    
+
+                // Freeze every caller sharing this gate for the server's
+                // penalty window instead of letting each one retry on its
+                // own schedule.
+                if let Some(duration) = retry_after {
+                    self.freeze.freeze_until(Instant::now() + duration);
+                }
+
                 Err(LlmError::RateLimited { retry_after })
             }
             StatusCode::PAYLOAD_TOO_LARGE => {
@@ -213,14 +473,18 @@ impl LlmClient {
         if retry_count >= self.max_retries {
             return false;
         }
-        
-        matches!(
+
+        let retryable = matches!(
             error,
             LlmError::RequestError(_) |
             LlmError::RateLimited { .. } |
             LlmError::ServiceUnavailable |
             LlmError::Timeout(_)
-        )
+        );
+
+        // Even a retryable error must pay for a spot in the shared retry
+        // budget; an exhausted bucket means stop and surface the last error.
+        retryable && self.retry_bucket.try_acquire(error)
     }
     
     fn calculate_retry_delay(&self, retry_count: u32, error: &LlmError) -> Duration {
@@ -236,6 +500,87 @@ impl LlmClient {
         }
     }
     
+    /// Stream a chat completion, feeding text deltas to `handler` as they arrive
+    /// and returning the fully accumulated reply text.
+    pub async fn chat_completion_stream(
+        &self,
+        mut request: ChatCompletionRequest,
+        handler: &mut dyn ReplyHandler,
+        cancellation_token: Option<CancellationToken>,
+    ) -> Result<String, LlmError> {
+        request.stream = Some(true);
+
+        if let Some(token) = &cancellation_token {
+            if token.is_cancelled() {
+                return Err(LlmError::Cancelled);
+            }
+        }
+
+        debug!("Sending streaming request to OpenAI API");
+
+        let response = self
+            .client
+            .post(&self.endpoint)
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .header("Content-Type", "application/json")
+            .json(&request)
+            .send()
+            .await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return self.handle_error_response(status, response).await.map(|_| String::new());
+        }
+
+        let mut stream = response.bytes_stream();
+        let mut line_buf = String::new();
+        let mut accumulated = String::new();
+
+        loop {
+            let chunk = if let Some(token) = &cancellation_token {
+                tokio::select! {
+                    chunk = stream.next() => chunk,
+                    _ = token.cancelled() => return Err(LlmError::Cancelled),
+                }
+            } else {
+                stream.next().await
+            };
+
+            let bytes = match chunk {
+                Some(Ok(bytes)) => bytes,
+                Some(Err(e)) => return Err(LlmError::RequestError(e)),
+                None => break,
+            };
+
+            line_buf.push_str(&String::from_utf8_lossy(&bytes));
+
+            while let Some(newline_pos) = line_buf.find('\n') {
+                let line = line_buf[..newline_pos].trim_end_matches('\r').to_string();
+                line_buf.drain(..=newline_pos);
+
+                let Some(payload) = line.strip_prefix("data: ") else {
+                    continue;
+                };
+
+                if payload == "[DONE]" {
+                    return Ok(accumulated);
+                }
+
+                let chunk: ChatCompletionChunk = serde_json::from_str(payload)
+                    .map_err(|e| LlmError::DeserializationError(format!("{}: {}", e, payload)))?;
+
+                if let Some(delta) = chunk.delta_text() {
+                    if !delta.is_empty() {
+                        handler.text(delta);
+                        accumulated.push_str(delta);
+                    }
+                }
+            }
+        }
+
+        Ok(accumulated)
+    }
+
     // Helper method to check if API key is valid without making a real request
     pub async fn validate_api_key(&self) -> Result<(), LlmError> {
         let request = ChatCompletionRequest::new("gpt-3.5-turbo")
@@ -262,6 +607,13 @@ mod tests {
             temperature: 0.3,
             max_retries: 3,
             timeout_seconds: 30,
+            provider: crate::config::ProviderKind::default(),
+            circuit_breaker_threshold: 5,
+            circuit_breaker_cooldown_seconds: 30,
+            retry_bucket_capacity: 500,
+            retry_bucket_timeout_cost: 5,
+            retry_bucket_throttle_cost: 10,
+            retry_bucket_refund: 1,
             api_key: None,
         };
         
@@ -292,6 +644,124 @@ mod tests {
         assert!(!client.should_retry(&LlmError::ServiceUnavailable, 3));
     }
 
+    #[test]
+    fn test_circuit_breaker_opens_after_threshold() {
+        let breaker = CircuitBreaker::new(3, Duration::from_secs(60));
+
+        assert!(breaker.guard().is_ok());
+        breaker.record_failure();
+        breaker.record_failure();
+        assert!(breaker.guard().is_ok());
+        breaker.record_failure();
+
+        assert!(breaker.guard().is_err());
+    }
+
+    #[test]
+    fn test_circuit_breaker_success_resets_failures() {
+        let breaker = CircuitBreaker::new(2, Duration::from_secs(60));
+
+        breaker.record_failure();
+        breaker.record_success();
+        breaker.record_failure();
+
+        // Only one consecutive failure since the reset, so still closed
+        assert!(breaker.guard().is_ok());
+    }
+
+    #[test]
+    fn test_circuit_breaker_half_open_after_cooldown() {
+        let breaker = CircuitBreaker::new(1, Duration::from_millis(0));
+
+        breaker.record_failure();
+        // Cooldown already elapsed, so guard() moves it to half-open and lets
+        // a probe through.
+        assert!(breaker.guard().is_ok());
+
+        // The probe failing reopens the breaker, but guard() still lets the
+        // next caller through once the (zero-length) cooldown elapses again.
+        breaker.record_failure();
+        assert!(breaker.guard().is_ok());
+    }
+
+    #[test]
+    fn test_retry_bucket_exhausts_and_refunds() {
+        let bucket = RetryTokenBucket::new(10, 5, 10, 5);
+
+        assert!(bucket.try_acquire(&LlmError::Timeout(30))); // 10 -> 5
+        assert!(bucket.try_acquire(&LlmError::Timeout(30))); // 5 -> 0
+        assert!(!bucket.try_acquire(&LlmError::Timeout(30))); // exhausted
+
+        bucket.refund(); // 0 -> 5
+        assert!(bucket.try_acquire(&LlmError::Timeout(30))); // 5 -> 0
+    }
+
+    #[test]
+    fn test_retry_bucket_refund_caps_at_capacity() {
+        let bucket = RetryTokenBucket::new(5, 5, 10, 1);
+
+        bucket.refund();
+        bucket.refund();
+
+        assert!(bucket.try_acquire(&LlmError::Timeout(30)));
+        assert!(!bucket.try_acquire(&LlmError::Timeout(30)));
+    }
+
+    #[test]
+    fn test_retry_bucket_non_retryable_error_costs_nothing() {
+        let bucket = RetryTokenBucket::new(0, 5, 10, 1);
+        assert!(bucket.try_acquire(&LlmError::InvalidApiKey));
+    }
+
+    #[test]
+    fn test_with_rate_limiter_attaches_limiter() {
+        let client = create_test_client().with_rate_limiter(Arc::new(RateLimiter::new(10, 100)));
+        assert!(client.rate_limiter.is_some());
+    }
+
+    #[test]
+    fn test_with_freeze_gate_shares_instance() {
+        let freeze = FreezeGate::new();
+        freeze.freeze_until(Instant::now() + Duration::from_secs(30));
+
+        let client = create_test_client().with_freeze_gate(freeze.clone());
+        assert!(client.freeze.remaining().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_chat_completion_cancel_during_freeze_skips_send_request() {
+        let client = create_test_client();
+        // Freeze far longer than the test should ever take, so a cancellation
+        // firing during the wait proves `chat_completion` never falls through
+        // to `send_request` (which would otherwise hang on a real network call).
+        client.freeze.freeze_until(Instant::now() + Duration::from_secs(30));
+
+        let token = CancellationToken::new();
+        let cancel_token = token.clone();
+        tokio::spawn(async move {
+            sleep(Duration::from_millis(10)).await;
+            cancel_token.cancel();
+        });
+
+        let result = client
+            .chat_completion(
+                ChatCompletionRequest::new("gpt-4.1-nano").with_user_message("hi"),
+                Some(token),
+            )
+            .await;
+
+        assert!(matches!(result, Err(LlmError::Cancelled)));
+    }
+
+    #[test]
+    fn test_reply_handler_closure() {
+        let mut collected = String::new();
+        let mut handler = |delta: &str| collected.push_str(delta);
+        handler.text("Hello");
+        handler.text(", world");
+        assert_eq!(collected, "Hello, world");
+    }
+
     #[test]
     fn test_retry_delay_calculation() {
         let client = create_test_client();
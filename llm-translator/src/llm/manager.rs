@@ -1,16 +1,65 @@
 use std::collections::{HashMap, VecDeque};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
-use tokio::sync::{Mutex, RwLock, mpsc};
-use tokio::time::interval;
+use tokio::sync::{broadcast, Mutex, RwLock, Semaphore, mpsc};
+use tokio::time::{interval, sleep};
 use tracing::{debug, error, info, warn};
 use dashmap::DashMap;
 use tokio_util::sync::CancellationToken;
 
-use super::{LlmClient, LlmError, ChatCompletionRequest, ChatMessage, TextSplitter, TranslationChunk, TranslatedChunk};
-use crate::validation::{RateLimiter, RateLimitError};
+use super::{LlmClient, LlmError, ChatCompletionRequest, ChatMessage, TextSplitter, TranslatedChunk};
+use super::spool::{unix_now, Spool, SpoolConfig, SpoolRecord};
+use crate::validation::{FreezeGate, RateLimiter, RateLimitError};
 use crate::config::Config;
 
+/// Broadcast channel capacity for fanning a request's result out to
+/// deduplicated callers - generous since it only needs to hold one value
+/// per subscriber that hasn't yet polled it.
+const DEDUP_CHANNEL_CAPACITY: usize = 16;
+
+/// Default cap on concurrently in-flight API calls, overridable via
+/// [`TranslationManager::with_max_concurrent`].
+const DEFAULT_MAX_CONCURRENT: usize = 10;
+
+/// Manager-level retry policy for transient failures that make it past
+/// `LlmClient`'s own internal retries (e.g. a `RateLimited` that survived
+/// because the client's shared retry budget was already spent). Retrying
+/// here re-queues the whole request instead of hammering the same call, so
+/// other queued work gets a turn in between attempts.
+#[derive(Debug, Clone)]
+pub struct RetryConfig {
+    pub max_retries: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(30),
+        }
+    }
+}
+
+impl RetryConfig {
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    pub fn with_base_delay(mut self, base_delay: Duration) -> Self {
+        self.base_delay = base_delay;
+        self
+    }
+
+    pub fn with_max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct TranslationRequest {
     pub id: String,
@@ -19,9 +68,12 @@ pub struct TranslationRequest {
     pub priority: RequestPriority,
     pub created_at: Instant,
     pub cancellation_token: CancellationToken,
+    /// How many times this request has already been retried after a
+    /// transient failure - compared against [`RetryConfig::max_retries`].
+    pub attempts: u32,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize)]
 pub enum RequestPriority {
     Low = 0,
     Normal = 1,
@@ -43,6 +95,13 @@ pub enum TranslationEvent {
     Failed { request_id: String, error: String },
     Cancelled { request_id: String },
     RateLimited { request_id: String, wait_time: Duration },
+    /// A request was split into chunks (see
+    /// [`TranslationManager::translate_in_chunks`]) and one more of them has
+    /// finished - `completed`/`total` let a caller show a progress bar.
+    Progress { request_id: String, completed: usize, total: usize },
+    /// Emitted once, at the start of [`TranslationManager::shutdown`], so a
+    /// consumer can show how much work is still in flight while it drains.
+    ShuttingDown { queued: usize, active: usize },
 }
 
 pub struct TranslationManager {
@@ -58,11 +117,46 @@ pub struct TranslationManager {
     cache: Arc<DashMap<String, (String, Instant)>>,
     cache_ttl: Duration,
     
-    // Deduplication
-    pending_hashes: Arc<DashMap<u64, Vec<String>>>, // hash -> request_ids
-    
+    // Deduplication - hash -> broadcaster for the in-flight request's result,
+    // so every duplicate that arrives while it's pending can subscribe and
+    // get its own event once the original finishes.
+    pending_hashes: Arc<DashMap<u64, broadcast::Sender<Result<TranslationResult, String>>>>,
+
     // Event channel
     event_sender: mpsc::Sender<TranslationEvent>,
+
+    /// Durable backing store for `queue`/`active_requests`, so a crash
+    /// doesn't silently drop what was in flight. A no-op store (the
+    /// default) keeps the manager purely in-memory.
+    spool: Spool,
+
+    retry_config: RetryConfig,
+
+    /// Caps concurrently in-flight `process_request` tasks - a permit is
+    /// acquired before a request is even popped off `queue`, so an empty
+    /// permit pool blocks dequeuing rather than blocking after the fact.
+    concurrency: Arc<Semaphore>,
+    max_concurrent: usize,
+
+    /// Cancelled by [`TranslationManager::shutdown`] to tell the processing
+    /// loop (and the cache-cleanup task) to stop.
+    shutdown_token: CancellationToken,
+    /// How long [`TranslationManager::shutdown`] waits for active requests
+    /// to finish on their own before cancelling them outright.
+    shutdown_grace: Duration,
+}
+
+/// Handle to the background task spawned by [`TranslationManager::start`].
+/// Join it after calling [`TranslationManager::shutdown`] to know the
+/// processing loop has actually exited.
+pub struct ShutdownHandle {
+    join_handle: tokio::task::JoinHandle<()>,
+}
+
+impl ShutdownHandle {
+    pub async fn join(self) {
+        let _ = self.join_handle.await;
+    }
 }
 
 impl TranslationManager {
@@ -72,19 +166,62 @@ impl TranslationManager {
         rate_limiter: RateLimiter,
         event_sender: mpsc::Sender<TranslationEvent>,
     ) -> Self {
+        // Share one freeze gate between the client and the rate limiter so a
+        // single 429's Retry-After pauses every queued request instead of
+        // each one backing off independently.
+        let freeze = FreezeGate::new();
+        let rate_limiter = Arc::new(rate_limiter.with_freeze_gate(freeze.clone()));
+        let client = client
+            .with_rate_limiter(Arc::clone(&rate_limiter))
+            .with_freeze_gate(freeze);
+
         Self {
             client: Arc::new(client),
             config,
-            rate_limiter: Arc::new(rate_limiter),
+            rate_limiter,
             queue: Arc::new(Mutex::new(VecDeque::new())),
             active_requests: Arc::new(DashMap::new()),
             cache: Arc::new(DashMap::new()),
             cache_ttl: Duration::from_secs(300), // 5 minutes
             pending_hashes: Arc::new(DashMap::new()),
             event_sender,
+            spool: Spool::new(SpoolConfig::default()),
+            retry_config: RetryConfig::default(),
+            concurrency: Arc::new(Semaphore::new(DEFAULT_MAX_CONCURRENT)),
+            max_concurrent: DEFAULT_MAX_CONCURRENT,
+            shutdown_token: CancellationToken::new(),
+            shutdown_grace: Duration::from_secs(30),
         }
     }
-    
+
+    /// Persist the queue to disk under `config` instead of keeping it
+    /// purely in-memory - call before [`TranslationManager::start`] so
+    /// recovery sees every file from the start.
+    pub fn with_spool_config(mut self, config: SpoolConfig) -> Self {
+        self.spool = Spool::new(config);
+        self
+    }
+
+    /// Override the default retry policy for transient request failures.
+    pub fn with_retry_config(mut self, retry_config: RetryConfig) -> Self {
+        self.retry_config = retry_config;
+        self
+    }
+
+    /// Cap the number of `process_request` calls in flight at once.
+    pub fn with_max_concurrent(mut self, max_concurrent: usize) -> Self {
+        self.max_concurrent = max_concurrent;
+        self.concurrency = Arc::new(Semaphore::new(max_concurrent));
+        self
+    }
+
+    /// Override how long [`TranslationManager::shutdown`] waits for active
+    /// requests to finish on their own before cancelling them.
+    pub fn with_shutdown_grace(mut self, shutdown_grace: Duration) -> Self {
+        self.shutdown_grace = shutdown_grace;
+        self
+    }
+
     /// Submit a translation request
     pub async fn translate(
         &self,
@@ -101,16 +238,14 @@ impl TranslationManager {
             return Ok(cached);
         }
         
-        // Check for duplicate pending requests
-        let text_hash = self.hash_text(&text);
-        if let Some(mut pending_ids) = self.pending_hashes.get_mut(&text_hash) {
+        // Check for duplicate pending requests - fan out the original's
+        // result to this request_id instead of making it wait on the queue.
+        if let Some(receiver) = self.subscribe_if_pending(&prompt_preset, &text) {
             debug!("Found pending request with same text, deduplicating");
-            pending_ids.push(request_id.clone());
-            // Wait for the original request to complete
-            // In real implementation, we'd use a more sophisticated notification system
-            return Err("Request deduplicated".to_string());
+            self.subscribe_duplicate(request_id.clone(), receiver);
+            return Ok(request_id);
         }
-        
+
         // Create new request
         let request = TranslationRequest {
             id: request_id.clone(),
@@ -119,16 +254,63 @@ impl TranslationManager {
             priority,
             created_at: Instant::now(),
             cancellation_token: CancellationToken::new(),
+            attempts: 0,
         };
-        
-        // Add to deduplication tracking
-        self.pending_hashes.insert(text_hash, vec![request_id.clone()]);
-        
-        // Add to queue
-        self.enqueue_request(request).await;
-        
+
+        self.enqueue_and_track(request).await;
+
         Ok(request_id)
     }
+
+    /// If a request with this exact `(prompt_preset, text)` pair is already
+    /// pending, return a receiver subscribed to its eventual result instead
+    /// of queuing a second copy. Keyed on both - not just `text` - for the
+    /// same reason `cache_key` is: the same text under a different preset
+    /// produces a different translation, so it must never share a result.
+    fn subscribe_if_pending(
+        &self,
+        prompt_preset: &str,
+        text: &str,
+    ) -> Option<broadcast::Receiver<Result<TranslationResult, String>>> {
+        self.pending_hashes.get(&self.dedup_key(prompt_preset, text)).map(|sender| sender.subscribe())
+    }
+
+    /// Register `request`'s `(prompt_preset, text)` hash in `pending_hashes`
+    /// and enqueue it - the dedup-tracking half of
+    /// [`TranslationManager::translate`], reused by
+    /// [`TranslationManager::translate_in_chunks`] for each sub-chunk.
+    async fn enqueue_and_track(&self, request: TranslationRequest) {
+        let dedup_key = self.dedup_key(&request.prompt_preset, &request.text);
+        let (result_sender, _) = broadcast::channel(DEDUP_CHANNEL_CAPACITY);
+        self.pending_hashes.insert(dedup_key, result_sender);
+        self.enqueue_request(request).await;
+    }
+
+    /// Wait for the in-flight request behind a duplicate's `dedup_key` to
+    /// finish, then deliver its outcome to `request_id` as its own
+    /// `Completed`/`Failed` event - so a caller tracking `request_id` sees a
+    /// normal lifecycle even though nothing was actually queued for it.
+    fn subscribe_duplicate(
+        &self,
+        request_id: String,
+        mut receiver: broadcast::Receiver<Result<TranslationResult, String>>,
+    ) {
+        let event_sender = self.event_sender.clone();
+        tokio::spawn(async move {
+            let event = match receiver.recv().await {
+                Ok(Ok(mut result)) => {
+                    result.request_id = request_id;
+                    TranslationEvent::Completed(result)
+                }
+                Ok(Err(error)) => TranslationEvent::Failed { request_id, error },
+                Err(_) => TranslationEvent::Failed {
+                    request_id,
+                    error: "original request dropped before completing".to_string(),
+                },
+            };
+            let _ = event_sender.send(event).await;
+        });
+    }
     
     /// Cancel a translation request
     pub async fn cancel(&self, request_id: &str) -> bool {
@@ -138,64 +320,175 @@ impl TranslationManager {
             let request = queue.remove(pos).unwrap();
             request.cancellation_token.cancel();
             drop(queue);
-            
+            self.spool.remove(request_id).await;
+
             self.send_event(TranslationEvent::Cancelled {
                 request_id: request_id.to_string(),
             }).await;
             return true;
         }
         drop(queue);
-        
+
         // Check if active
         if let Some((_, request)) = self.active_requests.remove(request_id) {
             request.cancellation_token.cancel();
+            self.spool.remove(request_id).await;
             self.send_event(TranslationEvent::Cancelled {
                 request_id: request_id.to_string(),
             }).await;
             return true;
         }
-        
+
         false
     }
-    
-    /// Start the translation processing loop
-    pub async fn start(self: Arc<Self>) {
+
+    /// Spawn the translation processing loop in the background and return a
+    /// handle to it. Call [`TranslationManager::shutdown`] to stop
+    /// gracefully, then [`ShutdownHandle::join`] to wait for the loop task
+    /// itself to exit.
+    pub fn start(self: Arc<Self>) -> ShutdownHandle {
+        let join_handle = tokio::spawn(self.run());
+        ShutdownHandle { join_handle }
+    }
+
+    async fn run(self: Arc<Self>) {
         info!("Translation manager started");
-        
-        // Start cache cleanup task
-        let manager = Arc::clone(&self);
+
+        self.recover_spool().await;
+
+        // Start cache cleanup task - it stops itself once `shutdown_token`
+        // is cancelled, same as the loop below.
+        let cleanup_manager = Arc::clone(&self);
+        let cleanup_token = self.shutdown_token.clone();
         tokio::spawn(async move {
-            manager.cache_cleanup_loop().await;
+            cleanup_manager.cache_cleanup_loop(cleanup_token).await;
         });
-        
+
         // Main processing loop
         let mut ticker = interval(Duration::from_millis(100));
-        
+
         loop {
-            ticker.tick().await;
-            
-            // Process queue
-            if let Some(request) = self.get_next_request().await {
-                let manager = Arc::clone(&self);
-                tokio::spawn(async move {
-                    manager.process_request(request).await;
-                });
+            tokio::select! {
+                _ = self.shutdown_token.cancelled() => break,
+                _ = ticker.tick() => {
+                    // Hold a concurrency permit before even checking the
+                    // rate limiter, so a request is popped off the queue
+                    // only once both a worker slot and a rate-limit slot
+                    // are available.
+                    let Ok(permit) = Arc::clone(&self.concurrency).try_acquire_owned() else {
+                        continue;
+                    };
+
+                    if let Some(request) = self.get_next_request().await {
+                        let manager = Arc::clone(&self);
+                        tokio::spawn(async move {
+                            let _permit = permit;
+                            manager.process_request(request).await;
+                        });
+                    }
+                    // Otherwise `permit` drops here and is returned to the pool.
+                }
             }
         }
+
+        info!("Translation manager processing loop stopped");
     }
-    
+
+    /// Stop dequeuing requests and wait up to `shutdown_grace` for
+    /// whatever's already active to finish, cancelling anything still
+    /// active once the grace period elapses. Requests still sitting in
+    /// `queue` need no extra handling here - every enqueued request is
+    /// already durably spooled (if a spool is configured) at the moment
+    /// it's queued, so they're recovered on the next startup either way.
+    /// [`TranslationManager::translate`] does not check for shutdown: calls
+    /// made after it still enqueue (and spool) their request, which is not
+    /// processed until the next startup.
+    pub async fn shutdown(&self) {
+        let queued = self.queue.lock().await.len();
+        let active = self.active_requests.len();
+        info!("Shutting down translation manager: {} queued, {} active", queued, active);
+        self.send_event(TranslationEvent::ShuttingDown { queued, active }).await;
+
+        self.shutdown_token.cancel();
+
+        let deadline = Instant::now() + self.shutdown_grace;
+        while !self.active_requests.is_empty() && Instant::now() < deadline {
+            sleep(Duration::from_millis(50)).await;
+        }
+
+        if !self.active_requests.is_empty() {
+            warn!(
+                "Shutdown grace period elapsed with {} request(s) still active - cancelling",
+                self.active_requests.len()
+            );
+            for entry in self.active_requests.iter() {
+                entry.cancellation_token.cancel();
+            }
+        }
+    }
+
     async fn enqueue_request(&self, request: TranslationRequest) {
+        let record = Self::to_spool_record(&request);
+        self.insert_into_queue(request).await;
+        self.spool.write(&record).await;
+    }
+
+    /// In-memory half of [`TranslationManager::enqueue_request`], split out
+    /// so spool recovery can re-populate the queue without re-persisting
+    /// requests that are already on disk.
+    async fn insert_into_queue(&self, request: TranslationRequest) {
         let mut queue = self.queue.lock().await;
-        
+
         // Insert based on priority (higher priority first)
         let insert_pos = queue.iter().position(|r| r.priority < request.priority)
             .unwrap_or(queue.len());
-            
+
         queue.insert(insert_pos, request);
-        
+
         debug!("Request enqueued, queue size: {}", queue.len());
     }
-    
+
+    fn to_spool_record(request: &TranslationRequest) -> SpoolRecord {
+        SpoolRecord {
+            id: request.id.clone(),
+            text: request.text.clone(),
+            prompt_preset: request.prompt_preset.clone(),
+            priority: request.priority,
+            created_at_unix: unix_now(),
+            attempts: request.attempts,
+        }
+    }
+
+    /// Re-enqueue anything the spool recovered from a crash, including
+    /// requests that were `active` (moved back to queued) at crash time -
+    /// called once from [`TranslationManager::start`] before the main loop.
+    async fn recover_spool(&self) {
+        let records = self.spool.recover().await;
+        if records.is_empty() {
+            return;
+        }
+
+        info!("Recovering {} request(s) from spool", records.len());
+        for record in records {
+            self.spool.mark_queued(&record.id).await;
+
+            let dedup_key = self.dedup_key(&record.prompt_preset, &record.text);
+            let (result_sender, _) = broadcast::channel(DEDUP_CHANNEL_CAPACITY);
+            self.pending_hashes.insert(dedup_key, result_sender);
+
+            let request = TranslationRequest {
+                id: record.id,
+                text: record.text,
+                prompt_preset: record.prompt_preset,
+                priority: record.priority,
+                created_at: Instant::now(),
+                cancellation_token: CancellationToken::new(),
+                attempts: record.attempts,
+            };
+            self.insert_into_queue(request).await;
+        }
+    }
+
     async fn get_next_request(&self) -> Option<TranslationRequest> {
         // Check rate limit
         if let Err(e) = self.rate_limiter.check_and_update() {
@@ -208,6 +501,14 @@ impl TranslationManager {
                     warn!("Daily limit reached");
                     return None;
                 }
+                RateLimitError::BucketLimit { bucket, wait_time } => {
+                    debug!("Rate limited on bucket '{}', waiting {:?}", bucket, wait_time);
+                    return None;
+                }
+                RateLimitError::Frozen { remaining } => {
+                    debug!("Rate limiter frozen, waiting {:?}", remaining);
+                    return None;
+                }
             }
         }
         
@@ -215,34 +516,35 @@ impl TranslationManager {
         queue.pop_front()
     }
     
-    async fn process_request(&self, request: TranslationRequest) {
+    async fn process_request(self: Arc<Self>, request: TranslationRequest) {
         let request_id = request.id.clone();
-        let text_hash = self.hash_text(&request.text);
-        
+        let dedup_key = self.dedup_key(&request.prompt_preset, &request.text);
+
         // Mark as active
         self.active_requests.insert(request_id.clone(), request.clone());
-        
+        self.spool.mark_active(&request_id).await;
+
         // Build API request
         let api_request = match self.build_api_request(&request).await {
             Ok(req) => req,
             Err(e) => {
                 error!("Failed to build API request: {}", e);
-                self.handle_request_failure(&request_id, e).await;
+                self.handle_request_failure(&request_id, dedup_key, e).await;
                 return;
             }
         };
-        
+
         // Send request
         let start_time = Instant::now();
-        match self.client.chat_completion(api_request, Some(request.cancellation_token)).await {
+        match self.client.chat_completion(api_request, Some(request.cancellation_token.clone())).await {
             Ok(response) => {
                 if let Some(translated) = response.get_content() {
                     let duration = start_time.elapsed();
-                    
+
                     // Cache the result
                     let cache_key = self.cache_key(&request.text, &request.prompt_preset);
                     self.cache.insert(cache_key, (translated.to_string(), Instant::now()));
-                    
+
                     // Create result
                     let result = TranslationResult {
                         request_id: request_id.clone(),
@@ -251,55 +553,228 @@ impl TranslationManager {
                         tokens_used: response.usage.total_tokens,
                         duration,
                     };
-                    
+
                     // Send completion event
-                    self.send_event(TranslationEvent::Completed(result)).await;
-                    
-                    // Notify deduplicated requests
-                    if let Some((_, pending_ids)) = self.pending_hashes.remove(&text_hash) {
-                        for id in pending_ids {
-                            if id != request_id {
-                                // In real implementation, notify these requests
-                                debug!("Notifying deduplicated request: {}", id);
-                            }
-                        }
+                    self.spool.remove(&request_id).await;
+                    // Fan the same result out to any duplicates that arrived
+                    // while this request was in flight, before moving it
+                    // into the event for the original caller.
+                    if let Some((_, sender)) = self.pending_hashes.remove(&dedup_key) {
+                        let _ = sender.send(Ok(result.clone()));
                     }
+                    self.send_event(TranslationEvent::Completed(result)).await;
                 } else {
                     self.handle_request_failure(
                         &request_id,
+                        dedup_key,
                         "No content in response".to_string()
                     ).await;
                 }
             }
             Err(e) => {
-                match &e {
-                    LlmError::RateLimited { retry_after } => {
-                        // Re-queue the request
-                        self.active_requests.remove(&request_id);
-                        self.enqueue_request(request).await;
-                        
+                if let LlmError::RequestTooLarge(tokens) = &e {
+                    warn!(
+                        "Request {} too large ({} tokens) - splitting into chunks",
+                        request_id, tokens
+                    );
+                    self.translate_in_chunks(request, dedup_key, start_time).await;
+                    return;
+                }
+
+                if Self::is_transient(&e) && request.attempts < self.retry_config.max_retries {
+                    let mut request = request;
+                    request.attempts += 1;
+                    let delay = self.retry_delay(request.attempts, &e);
+
+                    warn!(
+                        "Request {} failed (attempt {}/{}): {}. Retrying in {:?}",
+                        request_id, request.attempts, self.retry_config.max_retries, e, delay
+                    );
+
+                    // Re-queue the request - dedup tracking stays in place
+                    // since nothing has actually failed yet. Deliberately
+                    // left in `active_requests` through the backoff below,
+                    // so `shutdown()`'s grace-period wait still sees it as
+                    // in flight instead of returning immediately while it's
+                    // parked in `sleep`.
+                    self.spool.mark_queued(&request_id).await;
+
+                    if let LlmError::RateLimited { .. } = &e {
                         self.send_event(TranslationEvent::RateLimited {
-                            request_id,
-                            wait_time: retry_after.unwrap_or(Duration::from_secs(60)),
+                            request_id: request_id.clone(),
+                            wait_time: delay,
                         }).await;
                     }
-                    LlmError::RequestTooLarge(tokens) => {
-                        // Try to split the text
-                        warn!("Request too large: {} tokens", tokens);
-                        self.handle_request_failure(&request_id, e.to_string()).await;
-                    }
-                    _ => {
-                        self.handle_request_failure(&request_id, e.to_string()).await;
-                    }
+
+                    // Back off in a detached task instead of sleeping here,
+                    // so this task's worker-pool permit is released right
+                    // away rather than sitting idle for the whole delay - a
+                    // burst of transient failures would otherwise park every
+                    // permit in `sleep` and starve the queue. Race the
+                    // backoff against `shutdown_token` so a retry that's
+                    // still asleep when the process shuts down is failed
+                    // explicitly instead of silently dropped along with the
+                    // detached task.
+                    let manager = Arc::clone(&self);
+                    let shutdown_token = self.shutdown_token.clone();
+                    tokio::spawn(async move {
+                        tokio::select! {
+                            _ = sleep(delay) => {
+                                manager.active_requests.remove(&request_id);
+                                manager.insert_into_queue(request).await;
+                            }
+                            _ = shutdown_token.cancelled() => {
+                                manager.handle_request_failure(
+                                    &request_id,
+                                    dedup_key,
+                                    "Shutting down during retry backoff".to_string(),
+                                ).await;
+                            }
+                        }
+                    });
+                    return;
                 }
+
+                self.handle_request_failure(&request_id, dedup_key, e.to_string()).await;
             }
         }
-        
+
         // Clean up
         self.active_requests.remove(&request_id);
-        self.pending_hashes.remove(&text_hash);
     }
     
+    /// Transparently split an oversized request into chunks via
+    /// [`TextSplitter`] and translate each one as its own sub-request,
+    /// reassembling the results into a single [`TranslationResult`] for
+    /// `request`'s original caller. Triggered once `process_request` sees a
+    /// [`LlmError::RequestTooLarge`] for `request`. `request` stays in
+    /// `active_requests` for the whole reassembly so cancelling its id still
+    /// works - since every chunk shares its `cancellation_token`, one
+    /// `cancel()` call tears down every outstanding chunk too.
+    async fn translate_in_chunks(
+        &self,
+        request: TranslationRequest,
+        dedup_key: u64,
+        start_time: Instant,
+    ) {
+        let request_id = request.id.clone();
+
+        // No hard ceiling is known - `RequestTooLarge` only reports how many
+        // tokens the rejected attempt used - so fall back to the same
+        // tokens-to-chars ratio `TextValidator` already uses elsewhere and
+        // aim each chunk at the configured estimate.
+        let max_chunk_chars = {
+            let config = self.config.read().await;
+            config.limits.max_tokens_estimate.saturating_mul(4).max(1)
+        };
+        let splitter = TextSplitter::new(max_chunk_chars);
+        let source_chunks = splitter.split_for_translation(&request.text);
+        let total = source_chunks.len();
+
+        if total <= 1 {
+            self.handle_request_failure(
+                &request_id,
+                dedup_key,
+                "Request too large and could not be split further".to_string(),
+            ).await;
+            return;
+        }
+
+        info!("Splitting request {} into {} chunks", request_id, total);
+        self.send_event(TranslationEvent::Progress {
+            request_id: request_id.clone(),
+            completed: 0,
+            total,
+        }).await;
+
+        let mut translated_chunks = Vec::with_capacity(total);
+        let mut tokens_used = 0u32;
+
+        for source_chunk in source_chunks {
+            if request.cancellation_token.is_cancelled() {
+                self.handle_request_failure(&request_id, dedup_key, "Cancelled".to_string()).await;
+                return;
+            }
+
+            let chunk_request = TranslationRequest {
+                id: format!("{request_id}-chunk{}", source_chunk.index),
+                text: source_chunk.text,
+                prompt_preset: request.prompt_preset.clone(),
+                priority: request.priority,
+                created_at: Instant::now(),
+                cancellation_token: request.cancellation_token.clone(),
+                attempts: 0,
+            };
+
+            // Queue the chunk like any other request, so it's bound by the
+            // same rate limiter and concurrency permits - then wait for its
+            // result via the usual dedup broadcast instead of a fresh
+            // signal, reusing whatever's already wired up for duplicates.
+            let receiver = self.subscribe_if_pending(&chunk_request.prompt_preset, &chunk_request.text);
+            let mut receiver = match receiver {
+                Some(receiver) => receiver,
+                None => {
+                    let chunk_dedup_key = self.dedup_key(&chunk_request.prompt_preset, &chunk_request.text);
+                    let (result_sender, receiver) = broadcast::channel(DEDUP_CHANNEL_CAPACITY);
+                    self.pending_hashes.insert(chunk_dedup_key, result_sender);
+                    self.enqueue_request(chunk_request).await;
+                    receiver
+                }
+            };
+
+            match receiver.recv().await {
+                Ok(Ok(result)) => {
+                    tokens_used += result.tokens_used;
+                    translated_chunks.push(TranslatedChunk {
+                        index: source_chunk.index,
+                        translated_text: result.translated_text,
+                        overlap_start: source_chunk.overlap_start,
+                    });
+                    self.send_event(TranslationEvent::Progress {
+                        request_id: request_id.clone(),
+                        completed: translated_chunks.len(),
+                        total,
+                    }).await;
+                }
+                Ok(Err(error)) => {
+                    self.handle_request_failure(
+                        &request_id,
+                        dedup_key,
+                        format!("Chunk {} failed: {}", source_chunk.index, error),
+                    ).await;
+                    return;
+                }
+                Err(_) => {
+                    self.handle_request_failure(
+                        &request_id,
+                        dedup_key,
+                        format!("Chunk {} was dropped before completing", source_chunk.index),
+                    ).await;
+                    return;
+                }
+            }
+        }
+
+        let translated_text = splitter.merge_translations(translated_chunks);
+        let cache_key = self.cache_key(&request.text, &request.prompt_preset);
+        self.cache.insert(cache_key, (translated_text.clone(), Instant::now()));
+
+        let result = TranslationResult {
+            request_id: request_id.clone(),
+            original_text: request.text,
+            translated_text,
+            tokens_used,
+            duration: start_time.elapsed(),
+        };
+
+        self.spool.remove(&request_id).await;
+        if let Some((_, sender)) = self.pending_hashes.remove(&dedup_key) {
+            let _ = sender.send(Ok(result.clone()));
+        }
+        self.send_event(TranslationEvent::Completed(result)).await;
+        self.active_requests.remove(&request_id);
+    }
+
     async fn build_api_request(&self, request: &TranslationRequest) -> Result<ChatCompletionRequest, String> {
         let config = self.config.read().await;
         
@@ -315,11 +790,44 @@ impl TranslationManager {
             .with_user_id(&request.id))
     }
     
-    async fn handle_request_failure(&self, request_id: &str, error: String) {
+    /// Whether `error` is worth re-queuing rather than failing immediately -
+    /// mirrors `LlmClient::should_retry`'s classification, since these are
+    /// the same errors the client itself would retry if its own budget
+    /// weren't already spent.
+    fn is_transient(error: &LlmError) -> bool {
+        matches!(
+            error,
+            LlmError::RequestError(_)
+                | LlmError::RateLimited { .. }
+                | LlmError::ServiceUnavailable
+                | LlmError::Timeout(_)
+        )
+    }
+
+    /// Exponential backoff with jitter, capped at `retry_config.max_delay` -
+    /// matching `LlmClient::calculate_retry_delay`'s shape, except a 429's
+    /// own `Retry-After` takes priority over the computed delay when given.
+    fn retry_delay(&self, attempts: u32, error: &LlmError) -> Duration {
+        if let LlmError::RateLimited { retry_after: Some(duration) } = error {
+            return (*duration).min(self.retry_config.max_delay);
+        }
+
+        let exponential = self.retry_config.base_delay * 2u32.pow(attempts.saturating_sub(1));
+        let jitter = Duration::from_millis(fastrand::u64(0..100));
+        (exponential + jitter).min(self.retry_config.max_delay)
+    }
+
+    async fn handle_request_failure(&self, request_id: &str, dedup_key: u64, error: String) {
         error!("Request {} failed: {}", request_id, error);
-        
+
         self.active_requests.remove(request_id);
-        
+        self.spool.remove(request_id).await;
+
+        // Fan the failure out to any duplicates waiting on this dedup key.
+        if let Some((_, sender)) = self.pending_hashes.remove(&dedup_key) {
+            let _ = sender.send(Err(error.clone()));
+        }
+
         self.send_event(TranslationEvent::Failed {
             request_id: request_id.to_string(),
             error,
@@ -338,18 +846,21 @@ impl TranslationManager {
             .map(|entry| entry.0.clone())
     }
     
-    async fn cache_cleanup_loop(&self) {
+    async fn cache_cleanup_loop(&self, shutdown_token: CancellationToken) {
         let mut ticker = interval(Duration::from_secs(60));
-        
+
         loop {
-            ticker.tick().await;
-            
-            let now = Instant::now();
-            self.cache.retain(|_, (_, created)| {
-                now.duration_since(*created) < self.cache_ttl
-            });
-            
-            debug!("Cache cleanup: {} entries remaining", self.cache.len());
+            tokio::select! {
+                _ = shutdown_token.cancelled() => return,
+                _ = ticker.tick() => {
+                    let now = Instant::now();
+                    self.cache.retain(|_, (_, created)| {
+                        now.duration_since(*created) < self.cache_ttl
+                    });
+
+                    debug!("Cache cleanup: {} entries remaining", self.cache.len());
+                }
+            }
         }
     }
     
@@ -364,12 +875,26 @@ impl TranslationManager {
     fn hash_text(&self, text: &str) -> u64 {
         use std::collections::hash_map::DefaultHasher;
         use std::hash::{Hash, Hasher};
-        
+
         let mut hasher = DefaultHasher::new();
         text.hash(&mut hasher);
         hasher.finish()
     }
-    
+
+    /// Key for `pending_hashes` - hashes `prompt_preset` together with
+    /// `text`, same as `cache_key` does, so two in-flight requests for the
+    /// same text under different presets never get fanned out to each
+    /// other's result.
+    fn dedup_key(&self, prompt_preset: &str, text: &str) -> u64 {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+
+        let mut hasher = DefaultHasher::new();
+        prompt_preset.hash(&mut hasher);
+        text.hash(&mut hasher);
+        hasher.finish()
+    }
+
     /// Get queue statistics
     pub async fn get_stats(&self) -> QueueStats {
         let queue = self.queue.lock().await;
@@ -377,7 +902,9 @@ impl TranslationManager {
         QueueStats {
             queued: queue.len(),
             active: self.active_requests.len(),
+            max_concurrent: self.max_concurrent,
             cached: self.cache.len(),
+            spooled: self.spool.count(),
             rate_limit_remaining_minute: self.rate_limiter.remaining_this_minute(),
             rate_limit_remaining_day: self.rate_limiter.remaining_today(),
         }
@@ -388,7 +915,11 @@ impl TranslationManager {
 pub struct QueueStats {
     pub queued: usize,
     pub active: usize,
+    /// Worker-pool capacity `active` is measured against - see
+    /// [`TranslationManager::with_max_concurrent`].
+    pub max_concurrent: usize,
     pub cached: usize,
+    pub spooled: usize,
     pub rate_limit_remaining_minute: usize,
     pub rate_limit_remaining_day: usize,
 }
@@ -422,6 +953,7 @@ mod tests {
             priority: RequestPriority::Low,
             created_at: Instant::now(),
             cancellation_token: CancellationToken::new(),
+            attempts: 0,
         }).await;
         
         manager.enqueue_request(TranslationRequest {
@@ -431,6 +963,7 @@ mod tests {
             priority: RequestPriority::High,
             created_at: Instant::now(),
             cancellation_token: CancellationToken::new(),
+            attempts: 0,
         }).await;
         
         manager.enqueue_request(TranslationRequest {
@@ -440,6 +973,7 @@ mod tests {
             priority: RequestPriority::Normal,
             created_at: Instant::now(),
             cancellation_token: CancellationToken::new(),
+            attempts: 0,
         }).await;
         
         // High priority should be first
@@ -478,4 +1012,41 @@ mod tests {
         let key4 = manager.cache_key("Hello world", "general");
         assert_eq!(key1, key4);
     }
+
+    #[tokio::test]
+    async fn test_spool_recovery_repopulates_queue() {
+        let dir = std::env::temp_dir().join(format!("manager-spool-test-{}", unix_now()));
+        let spool_config = SpoolConfig::default().with_directory(&dir);
+
+        let (tx, _rx) = mpsc::channel(10);
+        let config = Arc::new(RwLock::new(Config::default()));
+        let client = LlmClient::new(&config.read().await.api, "test".to_string()).unwrap();
+        let rate_limiter = RateLimiter::new(10, 100);
+
+        let manager = TranslationManager::new(client, config.clone(), rate_limiter, tx)
+            .with_spool_config(spool_config.clone());
+        manager.enqueue_request(TranslationRequest {
+            id: "1".to_string(),
+            text: "test".to_string(),
+            prompt_preset: "general".to_string(),
+            priority: RequestPriority::Normal,
+            created_at: Instant::now(),
+            cancellation_token: CancellationToken::new(),
+            attempts: 0,
+        }).await;
+        assert_eq!(manager.get_stats().await.spooled, 1);
+
+        // Simulate a fresh process picking the spool back up.
+        let (tx2, _rx2) = mpsc::channel(10);
+        let client2 = LlmClient::new(&config.read().await.api, "test".to_string()).unwrap();
+        let rate_limiter2 = RateLimiter::new(10, 100);
+        let recovered_manager = TranslationManager::new(client2, config, rate_limiter2, tx2)
+            .with_spool_config(spool_config);
+        recovered_manager.recover_spool().await;
+
+        let next = recovered_manager.get_next_request().await.unwrap();
+        assert_eq!(next.id, "1");
+
+        tokio::fs::remove_dir_all(&dir).await.ok();
+    }
 }
\ No newline at end of file
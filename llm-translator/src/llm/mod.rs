@@ -1,9 +1,18 @@
 pub mod api_types;
 pub mod client;
 pub mod manager;
+pub mod provider;
+pub mod request_queue;
+pub mod spool;
 pub mod text_splitter;
 
 pub use api_types::*;
-pub use client::{LlmClient, LlmError};
-pub use manager::{TranslationManager, TranslationRequest};
-pub use text_splitter::{TextSplitter, TranslationChunk, TranslatedChunk};
\ No newline at end of file
+pub use client::{LlmClient, LlmError, ReplyHandler};
+pub use manager::{RetryConfig, ShutdownHandle, TranslationManager, TranslationRequest};
+pub use spool::{JsonSpoolSerializer, Spool, SpoolConfig, SpoolRecord, SpoolSerializer};
+pub use provider::{build_provider, AnthropicProvider, CompletionProvider, OpenAiCompatibleProvider};
+pub use request_queue::RequestQueue;
+pub use text_splitter::{
+    AbbreviationGuard, CjkSegmentation, DefaultSegmentation, SegmentationStrategy, TextSplitter,
+    TranslatedChunk, TranslationChunk,
+};
\ No newline at end of file
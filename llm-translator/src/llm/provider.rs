@@ -0,0 +1,412 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+use tracing::debug;
+
+use super::api_types::{
+    ChatChoice, ChatCompletionRequest, ChatCompletionResponse, ChatMessage, ChatMessageResponse, ContentPart, Usage,
+};
+use super::client::{LlmClient, LlmError, ReplyHandler};
+use crate::config::{ApiSettings, ProviderKind};
+
+/// Abstracts over different LLM backends so the crate isn't hard-wired to OpenAI.
+#[async_trait]
+pub trait CompletionProvider: Send + Sync {
+    async fn send(
+        &self,
+        request: ChatCompletionRequest,
+        cancellation_token: Option<CancellationToken>,
+    ) -> Result<ChatCompletionResponse, LlmError>;
+
+    async fn send_stream(
+        &self,
+        request: ChatCompletionRequest,
+        handler: &mut dyn ReplyHandler,
+        cancellation_token: Option<CancellationToken>,
+    ) -> Result<String, LlmError>;
+}
+
+/// Build the provider configured in `ApiSettings`.
+pub fn build_provider(settings: &ApiSettings, api_key: String) -> Result<Box<dyn CompletionProvider>, LlmError> {
+    match settings.provider {
+        ProviderKind::OpenAi => Ok(Box::new(OpenAiCompatibleProvider::new(settings, api_key)?)),
+        ProviderKind::Anthropic => Ok(Box::new(AnthropicProvider::new(settings, api_key))),
+    }
+}
+
+/// OpenAI-compatible provider: covers OpenAI itself, Ollama, and any custom
+/// endpoint that speaks the `/v1/chat/completions` dialect.
+pub struct OpenAiCompatibleProvider {
+    client: LlmClient,
+}
+
+impl OpenAiCompatibleProvider {
+    pub fn new(settings: &ApiSettings, api_key: String) -> Result<Self, LlmError> {
+        Ok(Self {
+            client: LlmClient::new(settings, api_key)?,
+        })
+    }
+}
+
+#[async_trait]
+impl CompletionProvider for OpenAiCompatibleProvider {
+    async fn send(
+        &self,
+        request: ChatCompletionRequest,
+        cancellation_token: Option<CancellationToken>,
+    ) -> Result<ChatCompletionResponse, LlmError> {
+        self.client.chat_completion(request, cancellation_token).await
+    }
+
+    async fn send_stream(
+        &self,
+        request: ChatCompletionRequest,
+        handler: &mut dyn ReplyHandler,
+        cancellation_token: Option<CancellationToken>,
+    ) -> Result<String, LlmError> {
+        self.client
+            .chat_completion_stream(request, handler, cancellation_token)
+            .await
+    }
+}
+
+/// Anthropic's Messages API: maps `ChatMessage::System` to the top-level
+/// `system` field and translates the remaining messages array.
+pub struct AnthropicProvider {
+    client: Client,
+    api_key: String,
+    endpoint: String,
+    model: String,
+}
+
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+
+/// Split a `data:<media-type>;base64,<data>` URL into its media type and
+/// base64 payload - the only image form Anthropic's `base64` source type
+/// accepts, and the only form [`ChatMessage::user_with_image`] ever produces.
+fn parse_data_url(url: &str) -> Option<(String, String)> {
+    let rest = url.strip_prefix("data:")?;
+    let (meta, data) = rest.split_once(',')?;
+    let media_type = meta.strip_suffix(";base64")?;
+    Some((media_type.to_string(), data.to_string()))
+}
+
+impl AnthropicProvider {
+    pub fn new(settings: &ApiSettings, api_key: String) -> Self {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(settings.timeout_seconds))
+            .build()
+            .unwrap_or_default();
+
+        Self {
+            client,
+            api_key,
+            endpoint: settings.endpoint.clone(),
+            model: settings.model.clone(),
+        }
+    }
+
+    fn build_request(&self, request: &ChatCompletionRequest, stream: bool) -> Result<AnthropicRequest, LlmError> {
+        let mut system = None;
+        let mut messages = Vec::new();
+
+        for message in &request.messages {
+            match message {
+                ChatMessage::System { .. } => system = Some(message.as_text()),
+                ChatMessage::User { content } => messages.push(AnthropicMessage {
+                    role: "user".to_string(),
+                    content: Self::build_content(content)?,
+                }),
+                ChatMessage::Assistant { content } => messages.push(AnthropicMessage {
+                    role: "assistant".to_string(),
+                    content: Self::build_content(content)?,
+                }),
+            }
+        }
+
+        Ok(AnthropicRequest {
+            model: request.model.clone(),
+            system,
+            messages,
+            max_tokens: request.max_tokens.unwrap_or(1024),
+            temperature: request.temperature,
+            stream: if stream { Some(true) } else { None },
+        })
+    }
+
+    /// Translate a message's `ContentPart`s into Anthropic's content-block
+    /// array, re-packaging any image into Anthropic's base64 `source` shape.
+    /// Errors if an image isn't a `data:` URL, since that's the only form
+    /// Anthropic's `base64` source type can carry.
+    fn build_content(parts: &[ContentPart]) -> Result<Vec<AnthropicMessageBlock>, LlmError> {
+        parts
+            .iter()
+            .map(|part| match part {
+                ContentPart::Text { text } => Ok(AnthropicMessageBlock::Text { text: text.clone() }),
+                ContentPart::ImageUrl { image_url } => {
+                    let (media_type, data) = parse_data_url(&image_url.url).ok_or_else(|| {
+                        LlmError::UnsupportedContent(
+                            "Anthropic provider only supports base64 data: image URLs".to_string(),
+                        )
+                    })?;
+                    Ok(AnthropicMessageBlock::Image {
+                        source: AnthropicImageSource {
+                            source_type: "base64".to_string(),
+                            media_type,
+                            data,
+                        },
+                    })
+                }
+            })
+            .collect()
+    }
+
+    fn to_chat_response(response: AnthropicResponse) -> ChatCompletionResponse {
+        let content = response
+            .content
+            .into_iter()
+            .find_map(|block| block.text)
+            .unwrap_or_default();
+
+        ChatCompletionResponse {
+            id: response.id,
+            object: "chat.completion".to_string(),
+            created: Utc::now().timestamp(),
+            model: response.model,
+            choices: vec![ChatChoice {
+                index: 0,
+                message: ChatMessageResponse {
+                    role: "assistant".to_string(),
+                    content,
+                },
+                finish_reason: response.stop_reason.unwrap_or_else(|| "stop".to_string()),
+            }],
+            usage: Usage {
+                prompt_tokens: response.usage.input_tokens,
+                completion_tokens: response.usage.output_tokens,
+                total_tokens: response.usage.input_tokens + response.usage.output_tokens,
+            },
+            system_fingerprint: None,
+        }
+    }
+}
+
+#[async_trait]
+impl CompletionProvider for AnthropicProvider {
+    async fn send(
+        &self,
+        request: ChatCompletionRequest,
+        cancellation_token: Option<CancellationToken>,
+    ) -> Result<ChatCompletionResponse, LlmError> {
+        if let Some(token) = &cancellation_token {
+            if token.is_cancelled() {
+                return Err(LlmError::Cancelled);
+            }
+        }
+
+        let body = self.build_request(&request, false)?;
+
+        debug!("Sending request to Anthropic API");
+
+        let response = self
+            .client
+            .post(&self.endpoint)
+            .header("x-api-key", &self.api_key)
+            .header("anthropic-version", ANTHROPIC_VERSION)
+            .header("content-type", "application/json")
+            .json(&body)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let message = response.text().await.unwrap_or_default();
+            return Err(LlmError::ApiError { message, code: None });
+        }
+
+        let parsed: AnthropicResponse = response
+            .json()
+            .await
+            .map_err(|e| LlmError::DeserializationError(e.to_string()))?;
+
+        Ok(Self::to_chat_response(parsed))
+    }
+
+    async fn send_stream(
+        &self,
+        request: ChatCompletionRequest,
+        handler: &mut dyn ReplyHandler,
+        cancellation_token: Option<CancellationToken>,
+    ) -> Result<String, LlmError> {
+        // Anthropic streams `content_block_delta` events rather than OpenAI's
+        // `choices[].delta`; fall back to a single non-streamed call and
+        // replay it through the handler so callers can treat both providers
+        // uniformly until a dedicated SSE parser is warranted.
+        let response = self.send(request, cancellation_token).await?;
+        let text = response.get_content().unwrap_or_default().to_string();
+        handler.text(&text);
+        Ok(text)
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct AnthropicRequest {
+    model: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    system: Option<String>,
+    messages: Vec<AnthropicMessage>,
+    max_tokens: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stream: Option<bool>,
+}
+
+#[derive(Debug, Serialize)]
+struct AnthropicMessage {
+    role: String,
+    content: Vec<AnthropicMessageBlock>,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum AnthropicMessageBlock {
+    Text { text: String },
+    Image { source: AnthropicImageSource },
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+struct AnthropicImageSource {
+    #[serde(rename = "type")]
+    source_type: String,
+    media_type: String,
+    data: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnthropicResponse {
+    id: String,
+    model: String,
+    content: Vec<AnthropicContentBlock>,
+    #[serde(default)]
+    stop_reason: Option<String>,
+    usage: AnthropicUsage,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnthropicContentBlock {
+    #[serde(default)]
+    text: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnthropicUsage {
+    input_tokens: u32,
+    output_tokens: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn anthropic_settings() -> ApiSettings {
+        ApiSettings {
+            endpoint: "https://api.anthropic.com/v1/messages".to_string(),
+            model: "claude-3-5-sonnet".to_string(),
+            temperature: 0.3,
+            max_retries: 3,
+            timeout_seconds: 30,
+            provider: ProviderKind::Anthropic,
+            circuit_breaker_threshold: 5,
+            circuit_breaker_cooldown_seconds: 30,
+            retry_bucket_capacity: 500,
+            retry_bucket_timeout_cost: 5,
+            retry_bucket_throttle_cost: 10,
+            retry_bucket_refund: 1,
+            api_key: None,
+        }
+    }
+
+    #[test]
+    fn test_build_request_splits_system_message() {
+        let provider = AnthropicProvider::new(&anthropic_settings(), "test-key".to_string());
+        let request = ChatCompletionRequest::new("claude-3-5-sonnet")
+            .with_system_message("You are a translator")
+            .with_user_message("Hello world");
+
+        let anthropic_request = provider.build_request(&request, false).unwrap();
+
+        assert_eq!(anthropic_request.system, Some("You are a translator".to_string()));
+        assert_eq!(anthropic_request.messages.len(), 1);
+        assert_eq!(anthropic_request.messages[0].role, "user");
+        assert_eq!(
+            anthropic_request.messages[0].content,
+            vec![AnthropicMessageBlock::Text { text: "Hello world".to_string() }]
+        );
+    }
+
+    #[test]
+    fn test_build_request_converts_image_to_base64_block() {
+        let provider = AnthropicProvider::new(&anthropic_settings(), "test-key".to_string());
+        let request = ChatCompletionRequest::new("claude-3-5-sonnet").with_message(ChatMessage::User {
+            content: vec![
+                ContentPart::text("describe this"),
+                ContentPart::image_url("data:image/png;base64,AAAA"),
+            ],
+        });
+
+        let anthropic_request = provider.build_request(&request, false).unwrap();
+
+        assert_eq!(
+            anthropic_request.messages[0].content,
+            vec![
+                AnthropicMessageBlock::Text { text: "describe this".to_string() },
+                AnthropicMessageBlock::Image {
+                    source: AnthropicImageSource {
+                        source_type: "base64".to_string(),
+                        media_type: "image/png".to_string(),
+                        data: "AAAA".to_string(),
+                    },
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_build_request_rejects_non_data_url_image() {
+        let provider = AnthropicProvider::new(&anthropic_settings(), "test-key".to_string());
+        let request = ChatCompletionRequest::new("claude-3-5-sonnet").with_message(ChatMessage::User {
+            content: vec![ContentPart::image_url("https://example.com/cat.png")],
+        });
+
+        let result = provider.build_request(&request, false);
+        assert!(matches!(result, Err(LlmError::UnsupportedContent(_))));
+    }
+
+    #[test]
+    fn test_build_provider_selects_openai_by_default() {
+        let settings = ApiSettings::default();
+        assert_eq!(settings.provider, ProviderKind::OpenAi);
+        assert!(build_provider(&settings, "test-key".to_string()).is_ok());
+    }
+
+    #[test]
+    fn test_build_provider_selects_anthropic() {
+        let settings = anthropic_settings();
+        assert!(build_provider(&settings, "test-key".to_string()).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_send_honors_cancelled_token() {
+        let provider = AnthropicProvider::new(&anthropic_settings(), "test-key".to_string());
+        let request = ChatCompletionRequest::new("claude-3-5-sonnet").with_user_message("Hello");
+
+        let token = CancellationToken::new();
+        token.cancel();
+
+        let result = provider.send(request, Some(token)).await;
+        assert!(matches!(result, Err(LlmError::Cancelled)));
+    }
+}
@@ -0,0 +1,221 @@
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::{oneshot, Mutex};
+use tokio::time::sleep;
+use tokio_util::sync::CancellationToken;
+use tracing::debug;
+
+use super::api_types::{ChatCompletionRequest, ChatCompletionResponse};
+use super::client::{LlmClient, LlmError};
+use crate::validation::RateLimiter;
+
+struct QueuedRequest {
+    id: u64,
+    request: ChatCompletionRequest,
+    cancellation_token: CancellationToken,
+    responder: oneshot::Sender<Result<ChatCompletionResponse, LlmError>>,
+}
+
+/// Schedules `chat_completion` calls against `rate_limiter`'s pacing instead
+/// of rejecting callers outright with `RateLimitError`: requests sit in a
+/// bounded FIFO deque until capacity frees up, then dispatch automatically.
+pub struct RequestQueue {
+    client: Arc<LlmClient>,
+    rate_limiter: Arc<RateLimiter>,
+    queue: Arc<Mutex<VecDeque<QueuedRequest>>>,
+    max_depth: usize,
+    next_id: AtomicU64,
+}
+
+impl RequestQueue {
+    pub fn new(client: Arc<LlmClient>, rate_limiter: Arc<RateLimiter>, max_depth: usize) -> Self {
+        Self {
+            client,
+            rate_limiter,
+            queue: Arc::new(Mutex::new(VecDeque::new())),
+            max_depth,
+            next_id: AtomicU64::new(0),
+        }
+    }
+
+    /// Enqueue a request and await its eventual dispatch. Gives callers
+    /// back-pressure and automatic pacing instead of having to implement
+    /// their own retry-on-429 loop.
+    pub async fn enqueue(
+        &self,
+        request: ChatCompletionRequest,
+        cancellation_token: CancellationToken,
+    ) -> Result<ChatCompletionResponse, LlmError> {
+        let (responder, receiver) = oneshot::channel();
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+
+        {
+            let mut queue = self.queue.lock().await;
+            if queue.len() >= self.max_depth {
+                return Err(LlmError::QueueFull { pending: queue.len() });
+            }
+            queue.push_back(QueuedRequest {
+                id,
+                request,
+                cancellation_token: cancellation_token.clone(),
+                responder,
+            });
+        }
+
+        // Watch for cancellation while the request is still sitting in the
+        // deque and remove it without dispatching, rather than relying on
+        // the dispatch loop to notice after the fact.
+        let queue = Arc::clone(&self.queue);
+        tokio::spawn(async move {
+            cancellation_token.cancelled().await;
+            let mut queue = queue.lock().await;
+            if let Some(pos) = queue.iter().position(|queued| queued.id == id) {
+                let queued = queue.remove(pos).expect("position just found");
+                let _ = queued.responder.send(Err(LlmError::Cancelled));
+            }
+        });
+
+        receiver.await.unwrap_or(Err(LlmError::Cancelled))
+    }
+
+    /// Run the dispatch loop: pop the front request, wait until
+    /// `rate_limiter` has room, send it, and fulfill the waiting caller.
+    /// Intended to be spawned once as a long-running background task.
+    pub async fn run(self: Arc<Self>) {
+        loop {
+            let queued = {
+                let mut queue = self.queue.lock().await;
+                queue.pop_front()
+            };
+
+            let Some(queued) = queued else {
+                sleep(Duration::from_millis(50)).await;
+                continue;
+            };
+
+            if queued.cancellation_token.is_cancelled() {
+                let _ = queued.responder.send(Err(LlmError::Cancelled));
+                continue;
+            }
+
+            if let Some(wait) = self.rate_limiter.next_available() {
+                debug!("Request queue waiting {:?} for rate-limit capacity", wait);
+                tokio::select! {
+                    _ = sleep(wait) => {},
+                    _ = queued.cancellation_token.cancelled() => {
+                        let _ = queued.responder.send(Err(LlmError::Cancelled));
+                        continue;
+                    }
+                }
+            }
+
+            let result = self
+                .client
+                .chat_completion(queued.request, Some(queued.cancellation_token))
+                .await;
+            let _ = queued.responder.send(result);
+        }
+    }
+
+    pub async fn len(&self) -> usize {
+        self.queue.lock().await.len()
+    }
+
+    pub async fn is_empty(&self) -> bool {
+        self.len().await == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ApiSettings;
+
+    fn test_client() -> Arc<LlmClient> {
+        let settings = ApiSettings {
+            endpoint: "https://api.openai.com/v1/chat/completions".to_string(),
+            model: "gpt-4.1-nano".to_string(),
+            temperature: 0.3,
+            max_retries: 3,
+            timeout_seconds: 30,
+            provider: crate::config::ProviderKind::default(),
+            circuit_breaker_threshold: 5,
+            circuit_breaker_cooldown_seconds: 30,
+            retry_bucket_capacity: 500,
+            retry_bucket_timeout_cost: 5,
+            retry_bucket_throttle_cost: 10,
+            retry_bucket_refund: 1,
+            api_key: None,
+        };
+
+        Arc::new(LlmClient::new(&settings, "test-key".to_string()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_enqueue_rejects_when_full() {
+        let queue = Arc::new(RequestQueue::new(
+            test_client(),
+            Arc::new(RateLimiter::new(1000, 1000)),
+            1,
+        ));
+
+        // Fill the queue without a dispatch loop running, so the first
+        // request sits pending and the second should be rejected.
+        let queue_for_fill = Arc::clone(&queue);
+        tokio::spawn(async move {
+            let _ = queue_for_fill
+                .enqueue(
+                    ChatCompletionRequest::new("gpt-4.1-nano").with_user_message("hi"),
+                    CancellationToken::new(),
+                )
+                .await;
+        });
+
+        // Give the spawned task a moment to push into the deque.
+        sleep(Duration::from_millis(10)).await;
+
+        let result = queue
+            .enqueue(
+                ChatCompletionRequest::new("gpt-4.1-nano").with_user_message("hi"),
+                CancellationToken::new(),
+            )
+            .await;
+
+        assert!(matches!(result, Err(LlmError::QueueFull { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_cancellation_removes_pending_request_without_dispatch() {
+        let queue = Arc::new(RequestQueue::new(
+            test_client(),
+            Arc::new(RateLimiter::new(1000, 1000)),
+            10,
+        ));
+
+        let token = CancellationToken::new();
+        let queue_for_enqueue = Arc::clone(&queue);
+        let token_for_enqueue = token.clone();
+        let handle = tokio::spawn(async move {
+            queue_for_enqueue
+                .enqueue(
+                    ChatCompletionRequest::new("gpt-4.1-nano").with_user_message("hi"),
+                    token_for_enqueue,
+                )
+                .await
+        });
+
+        // Give the request a moment to land in the deque, then cancel it
+        // before any dispatch loop would ever pop it (none is running here).
+        sleep(Duration::from_millis(10)).await;
+        assert_eq!(queue.len().await, 1);
+
+        token.cancel();
+        let result = handle.await.unwrap();
+
+        assert!(matches!(result, Err(LlmError::Cancelled)));
+        assert_eq!(queue.len().await, 0);
+    }
+}
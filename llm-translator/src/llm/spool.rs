@@ -0,0 +1,314 @@
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use super::manager::RequestPriority;
+
+/// Where (and how) queued/active requests are persisted so a process
+/// restart can recover them. `directory: None` disables the spool entirely
+/// - every [`Spool`] operation becomes a no-op and the queue stays purely
+/// in-memory, matching the manager's behavior before this existed.
+#[derive(Debug, Clone)]
+pub struct SpoolConfig {
+    pub directory: Option<PathBuf>,
+    pub max_size: usize,
+    pub fsync: bool,
+}
+
+impl Default for SpoolConfig {
+    fn default() -> Self {
+        Self {
+            directory: None,
+            max_size: 10_000,
+            fsync: false,
+        }
+    }
+}
+
+impl SpoolConfig {
+    pub fn with_directory(mut self, directory: impl Into<PathBuf>) -> Self {
+        self.directory = Some(directory.into());
+        self
+    }
+
+    pub fn with_max_size(mut self, max_size: usize) -> Self {
+        self.max_size = max_size;
+        self
+    }
+
+    pub fn with_fsync(mut self, fsync: bool) -> Self {
+        self.fsync = fsync;
+        self
+    }
+}
+
+/// On-disk form of a [`super::manager::TranslationRequest`] - `created_at`
+/// is recorded as a wall-clock Unix timestamp since `Instant` can't be
+/// serialized or survive a restart.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpoolRecord {
+    pub id: String,
+    pub text: String,
+    pub prompt_preset: String,
+    pub priority: RequestPriority,
+    pub created_at_unix: u64,
+    /// Retry count carried over so a crash mid-backoff doesn't reset a
+    /// request's attempt budget. Defaults to 0 for spool files written
+    /// before this field existed.
+    #[serde(default)]
+    pub attempts: u32,
+}
+
+/// Turns a [`SpoolRecord`] into file bytes and back. JSON by default;
+/// swappable so a deployment can plug in a denser or encrypted format
+/// without touching [`Spool`] itself.
+pub trait SpoolSerializer: Send + Sync {
+    fn serialize(&self, record: &SpoolRecord) -> std::io::Result<Vec<u8>>;
+    fn deserialize(&self, bytes: &[u8]) -> std::io::Result<SpoolRecord>;
+}
+
+pub struct JsonSpoolSerializer;
+
+impl SpoolSerializer for JsonSpoolSerializer {
+    fn serialize(&self, record: &SpoolRecord) -> std::io::Result<Vec<u8>> {
+        serde_json::to_vec_pretty(record).map_err(std::io::Error::from)
+    }
+
+    fn deserialize(&self, bytes: &[u8]) -> std::io::Result<SpoolRecord> {
+        serde_json::from_slice(bytes).map_err(std::io::Error::from)
+    }
+}
+
+/// Durable mail-queue-style spool: each queued request is one file under
+/// `directory`, moved into `directory/active` while in flight, and deleted
+/// on terminal completion/cancellation. [`Spool::recover`] is called once
+/// at startup to re-enqueue anything left over from a crash.
+pub struct Spool {
+    config: SpoolConfig,
+    serializer: Box<dyn SpoolSerializer>,
+    on_disk_count: AtomicUsize,
+}
+
+impl Spool {
+    pub fn new(config: SpoolConfig) -> Self {
+        Self::with_serializer(config, Box::new(JsonSpoolSerializer))
+    }
+
+    pub fn with_serializer(config: SpoolConfig, serializer: Box<dyn SpoolSerializer>) -> Self {
+        Self {
+            config,
+            serializer,
+            on_disk_count: AtomicUsize::new(0),
+        }
+    }
+
+    fn active_dir(&self) -> Option<PathBuf> {
+        self.config.directory.as_ref().map(|dir| dir.join("active"))
+    }
+
+    fn queued_path(&self, id: &str) -> Option<PathBuf> {
+        self.config.directory.as_ref().map(|dir| dir.join(format!("{id}.json")))
+    }
+
+    fn active_path(&self, id: &str) -> Option<PathBuf> {
+        self.active_dir().map(|dir| dir.join(format!("{id}.json")))
+    }
+
+    /// Current on-disk request count, queued plus active.
+    pub fn count(&self) -> usize {
+        self.on_disk_count.load(Ordering::Relaxed)
+    }
+
+    /// Persist a newly queued request. Best-effort: a write failure (or a
+    /// spool already at `max_size`) is logged and otherwise ignored, since
+    /// losing durability shouldn't stop the request from being processed.
+    pub async fn write(&self, record: &SpoolRecord) {
+        let Some(directory) = self.config.directory.as_ref() else { return };
+        if self.count() >= self.config.max_size {
+            warn!(
+                "Spool at max_size ({}) - not persisting request {}",
+                self.config.max_size, record.id
+            );
+            return;
+        }
+
+        if let Err(e) = tokio::fs::create_dir_all(directory).await {
+            warn!("Failed to create spool directory: {}", e);
+            return;
+        }
+
+        let bytes = match self.serializer.serialize(record) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                warn!("Failed to serialize spool record {}: {}", record.id, e);
+                return;
+            }
+        };
+
+        let path = directory.join(format!("{}.json", record.id));
+        if let Err(e) = tokio::fs::write(&path, &bytes).await {
+            warn!("Failed to write spool file for {}: {}", record.id, e);
+            return;
+        }
+
+        if self.config.fsync {
+            if let Ok(file) = tokio::fs::File::open(&path).await {
+                let _ = file.sync_all().await;
+            }
+        }
+
+        self.on_disk_count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Move a queued request's spool file into `active/`, so a crash while
+    /// it's in flight is recovered as still-pending rather than lost.
+    pub async fn mark_active(&self, id: &str) {
+        let (Some(from), Some(to)) = (self.queued_path(id), self.active_path(id)) else { return };
+        if let Some(dir) = self.active_dir() {
+            let _ = tokio::fs::create_dir_all(&dir).await;
+        }
+        let _ = tokio::fs::rename(&from, &to).await;
+    }
+
+    /// Move a request's spool file back out of `active/` - e.g. when it's
+    /// re-queued after a rate limit instead of completing, or recovered at
+    /// startup from a crash mid-flight.
+    pub async fn mark_queued(&self, id: &str) {
+        let (Some(from), Some(to)) = (self.active_path(id), self.queued_path(id)) else { return };
+        let _ = tokio::fs::rename(&from, &to).await;
+    }
+
+    /// Remove a request's spool file on terminal completion, failure, or
+    /// cancellation - wherever it currently lives.
+    pub async fn remove(&self, id: &str) {
+        if self.config.directory.is_none() {
+            return;
+        }
+
+        let mut removed = false;
+        if let Some(path) = self.queued_path(id) {
+            if tokio::fs::remove_file(&path).await.is_ok() {
+                removed = true;
+            }
+        }
+        if let Some(path) = self.active_path(id) {
+            if tokio::fs::remove_file(&path).await.is_ok() {
+                removed = true;
+            }
+        }
+        if removed {
+            self.on_disk_count.fetch_sub(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Scan the spool directory at startup, returning every recoverable
+    /// record - both still-queued ones and ones that were `active` at
+    /// crash time, which are reported the same way so the caller re-queues
+    /// them at their original priority.
+    pub async fn recover(&self) -> Vec<SpoolRecord> {
+        let Some(directory) = self.config.directory.as_ref() else { return Vec::new() };
+
+        let mut records = Vec::new();
+        for dir in [directory.clone(), directory.join("active")] {
+            let Ok(mut entries) = tokio::fs::read_dir(&dir).await else { continue };
+            while let Ok(Some(entry)) = entries.next_entry().await {
+                let path = entry.path();
+                if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                    continue;
+                }
+                let Ok(bytes) = tokio::fs::read(&path).await else { continue };
+                match self.serializer.deserialize(&bytes) {
+                    Ok(record) => records.push(record),
+                    Err(e) => warn!("Failed to parse spool file {:?}: {}", path, e),
+                }
+            }
+        }
+
+        self.on_disk_count.store(records.len(), Ordering::Relaxed);
+        records
+    }
+}
+
+/// Wall-clock Unix timestamp for a [`SpoolRecord`] created right now.
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(id: &str) -> SpoolRecord {
+        SpoolRecord {
+            id: id.to_string(),
+            text: "hello".to_string(),
+            prompt_preset: "general".to_string(),
+            priority: RequestPriority::Normal,
+            created_at_unix: unix_now(),
+            attempts: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_disabled_spool_is_a_no_op() {
+        let spool = Spool::new(SpoolConfig::default());
+        spool.write(&record("1")).await;
+        assert_eq!(spool.count(), 0);
+        assert!(spool.recover().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_write_then_recover_round_trips() {
+        let dir = std::env::temp_dir().join(format!("spool-test-{}", unix_now()));
+        let config = SpoolConfig::default().with_directory(&dir);
+        let spool = Spool::new(config);
+
+        spool.write(&record("1")).await;
+        assert_eq!(spool.count(), 1);
+
+        let recovered = spool.recover().await;
+        assert_eq!(recovered.len(), 1);
+        assert_eq!(recovered[0].id, "1");
+
+        tokio::fs::remove_dir_all(&dir).await.ok();
+    }
+
+    #[tokio::test]
+    async fn test_mark_active_then_recover_then_remove() {
+        let dir = std::env::temp_dir().join(format!("spool-test-active-{}", unix_now()));
+        let config = SpoolConfig::default().with_directory(&dir);
+        let spool = Spool::new(config);
+
+        spool.write(&record("1")).await;
+        spool.mark_active("1").await;
+
+        // Still recoverable while "active" at crash time.
+        let recovered = spool.recover().await;
+        assert_eq!(recovered.len(), 1);
+
+        spool.remove("1").await;
+        assert_eq!(spool.count(), 0);
+        assert!(spool.recover().await.is_empty());
+
+        tokio::fs::remove_dir_all(&dir).await.ok();
+    }
+
+    #[tokio::test]
+    async fn test_max_size_stops_persisting() {
+        let dir = std::env::temp_dir().join(format!("spool-test-maxsize-{}", unix_now()));
+        let config = SpoolConfig::default().with_directory(&dir).with_max_size(1);
+        let spool = Spool::new(config);
+
+        spool.write(&record("1")).await;
+        spool.write(&record("2")).await;
+        assert_eq!(spool.count(), 1);
+
+        tokio::fs::remove_dir_all(&dir).await.ok();
+    }
+}
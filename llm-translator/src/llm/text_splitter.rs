@@ -1,9 +1,13 @@
+use similar::{DiffOp, TextDiff};
+
 use crate::validation::TextValidator;
 
 /// Split large text into smaller chunks for translation
 pub struct TextSplitter {
     max_chunk_size: usize,
     overlap_size: usize,
+    min_overlap_match: usize,
+    tail_window: usize,
 }
 
 impl TextSplitter {
@@ -11,11 +15,43 @@ impl TextSplitter {
         Self {
             max_chunk_size,
             overlap_size: 50, // Characters to overlap between chunks for context
+            min_overlap_match: 6,
+            tail_window: 200,
         }
     }
-    
-    /// Split text into translatable chunks while preserving context
+
+    /// Shortest run of matching characters `merge_translations` will accept
+    /// as genuine overlap rather than a coincidental match. Below this, a
+    /// continuation chunk's text is kept whole and just space-joined.
+    pub fn with_min_overlap_match(mut self, chars: usize) -> Self {
+        self.min_overlap_match = chars;
+        self
+    }
+
+    /// How many trailing characters of the previous chunk's translation to
+    /// search when looking for the overlap run. Bounds the diff cost on long
+    /// chunks - the overlap can only ever be `overlap_size` source characters
+    /// wide, so its translation shouldn't be much longer than that either.
+    pub fn with_tail_window(mut self, chars: usize) -> Self {
+        self.tail_window = chars;
+        self
+    }
+
+    /// Split text into translatable chunks while preserving context, using
+    /// the default sentence-boundary heuristic to pick chunk edges.
     pub fn split_for_translation(&self, text: &str) -> Vec<TranslationChunk> {
+        self.split_for_translation_with(text, &DefaultSegmentation)
+    }
+
+    /// Same as [`TextSplitter::split_for_translation`] but with an explicit
+    /// [`SegmentationStrategy`] for scoring candidate chunk boundaries - e.g.
+    /// [`CjkSegmentation`] for scripts without spaces, or wrapped in an
+    /// [`AbbreviationGuard`] to avoid splitting on `Dr.`/`e.g.`-style periods.
+    pub fn split_for_translation_with(
+        &self,
+        text: &str,
+        strategy: &dyn SegmentationStrategy,
+    ) -> Vec<TranslationChunk> {
         if text.chars().count() <= self.max_chunk_size {
             return vec![TranslationChunk {
                 index: 0,
@@ -24,53 +60,141 @@ impl TextSplitter {
                 overlap_start: 0,
             }];
         }
-        
+
         let mut chunks = Vec::new();
         let mut current_position = 0;
         let chars: Vec<char> = text.chars().collect();
         let total_chars = chars.len();
         let mut chunk_index = 0;
-        
+
         while current_position < total_chars {
             // Calculate chunk boundaries
-            let chunk_start = if chunk_index == 0 { 
-                0 
-            } else { 
-                current_position.saturating_sub(self.overlap_size) 
+            let chunk_start = if chunk_index == 0 {
+                0
+            } else {
+                current_position.saturating_sub(self.overlap_size)
             };
-            
+
             let chunk_end = (chunk_start + self.max_chunk_size).min(total_chars);
-            
+
             // Try to find a good split point (sentence boundary)
             let adjusted_end = if chunk_end < total_chars {
-                self.find_split_point(&chars, chunk_start, chunk_end)
+                strategy
+                    .find_split_point(&chars, chunk_start, chunk_end)
                     .unwrap_or(chunk_end)
             } else {
                 chunk_end
             };
-            
+
             // Extract chunk text
             let chunk_text: String = chars[chunk_start..adjusted_end].iter().collect();
-            
+
             chunks.push(TranslationChunk {
                 index: chunk_index,
                 text: chunk_text,
                 is_continuation: chunk_index > 0,
                 overlap_start: if chunk_index > 0 { self.overlap_size } else { 0 },
             });
-            
+
             current_position = adjusted_end;
             chunk_index += 1;
         }
-        
+
         chunks
     }
-    
-    /// Find a good split point near the target position
+
+    /// Merge translated chunks back together
+    pub fn merge_translations(&self, chunks: Vec<TranslatedChunk>) -> String {
+        if chunks.is_empty() {
+            return String::new();
+        }
+
+        if chunks.len() == 1 {
+            return chunks[0].translated_text.clone();
+        }
+
+        let mut result = String::new();
+
+        for (i, chunk) in chunks.iter().enumerate() {
+            if i == 0 {
+                result.push_str(&chunk.translated_text);
+            } else {
+                // Remove overlap from the beginning of continuation chunks
+                let text = if chunk.overlap_start > 0 {
+                    self.reconcile_overlap(&chunks[i - 1].translated_text, &chunk.translated_text)
+                } else {
+                    chunk.translated_text.clone()
+                };
+
+                // Add appropriate spacing
+                if !result.ends_with(char::is_whitespace) && !text.starts_with(char::is_whitespace) {
+                    result.push(' ');
+                }
+
+                result.push_str(&text);
+            }
+        }
+
+        result
+    }
+
+    /// Find where `current`'s translation re-states the tail of `previous`'s
+    /// translation and drop that shared run from `current`'s front.
+    ///
+    /// The two chunks were translated independently from overlapping source
+    /// text, so the translated overlap rarely lines up as an exact prefix
+    /// match the way the source characters did - wording, spacing and
+    /// punctuation can all drift slightly. Diffing the tail of `previous`
+    /// against all of `current` finds the longest run they still agree on
+    /// and anchors on it ending at the very end of that tail, which is where
+    /// the shared source text was. If nothing long enough lines up, the
+    /// chunk is kept whole and `merge_translations` just space-joins it.
+    fn reconcile_overlap(&self, previous: &str, current: &str) -> String {
+        let previous_chars: Vec<char> = previous.chars().collect();
+        let tail_start = previous_chars.len().saturating_sub(self.tail_window);
+        let tail: String = previous_chars[tail_start..].iter().collect();
+        let tail_len = tail.chars().count();
+
+        let diff = TextDiff::from_chars(&tail, current);
+        let best_match = diff
+            .ops()
+            .iter()
+            .filter_map(|op| match *op {
+                DiffOp::Equal { old_index, new_index, len }
+                    if len >= self.min_overlap_match && old_index + len == tail_len =>
+                {
+                    Some((new_index, len))
+                }
+                _ => None,
+            })
+            .max_by_key(|(_, len)| *len);
+
+        match best_match {
+            Some((new_index, len)) => current.chars().skip(new_index + len).collect(),
+            None => current.to_string(),
+        }
+    }
+}
+
+/// Scores candidate chunk boundaries for [`TextSplitter::split_for_translation_with`].
+/// `find_split_point` searches backwards from `target` within `start..=target`
+/// and returns the best index to cut at, or `None` if nothing in range looks
+/// like a genuine boundary.
+pub trait SegmentationStrategy {
+    fn find_split_point(&self, chars: &[char], start: usize, target: usize) -> Option<usize>;
+}
+
+/// The original heuristic: sentence terminators, then paragraph breaks, then
+/// soft punctuation, then plain word boundaries. Works well for
+/// space-delimited languages but treats every `.` as a sentence end and
+/// can't split scripts that don't use spaces or Western punctuation.
+pub struct DefaultSegmentation;
+
+impl SegmentationStrategy for DefaultSegmentation {
     fn find_split_point(&self, chars: &[char], start: usize, target: usize) -> Option<usize> {
         // Look for sentence boundaries first
         let sentence_endings = ['.', '!', '?', '。', '！', '？'];
-        
+
         // Search backwards from target for sentence ending
         for i in (start..=target).rev() {
             if i > start && sentence_endings.contains(&chars[i - 1]) {
@@ -80,14 +204,14 @@ impl TextSplitter {
                 }
             }
         }
-        
+
         // Look for paragraph boundaries
         for i in (start..=target).rev() {
             if i > start && chars[i - 1] == '\n' {
                 return Some(i);
             }
         }
-        
+
         // Look for other natural boundaries (comma, semicolon)
         let soft_boundaries = [',', ';', ':', '、', '；', '：'];
         for i in (start..=target).rev() {
@@ -95,66 +219,103 @@ impl TextSplitter {
                 return Some(i);
             }
         }
-        
+
         // Last resort: find word boundary
         for i in (start..=target).rev() {
             if i > start && chars[i].is_whitespace() && !chars[i - 1].is_whitespace() {
                 return Some(i);
             }
         }
-        
+
         None
     }
-    
-    /// Merge translated chunks back together
-    pub fn merge_translations(&self, chunks: Vec<TranslatedChunk>) -> String {
-        if chunks.is_empty() {
-            return String::new();
-        }
-        
-        if chunks.len() == 1 {
-            return chunks[0].translated_text.clone();
+}
+
+/// Favors full-width sentence/clause punctuation, then falls back to
+/// splitting after any CJK ideograph near `target` - these scripts are
+/// commonly written without spaces, so the word-boundary fallback in
+/// [`DefaultSegmentation`] never fires and chunks would otherwise only ever
+/// split at `target` itself.
+pub struct CjkSegmentation;
+
+impl CjkSegmentation {
+    fn is_cjk_ideograph(c: char) -> bool {
+        matches!(c as u32,
+            0x4E00..=0x9FFF   // CJK Unified Ideographs
+            | 0x3400..=0x4DBF // CJK Unified Ideographs Extension A
+            | 0x3040..=0x30FF // Hiragana + Katakana
+        )
+    }
+}
+
+impl SegmentationStrategy for CjkSegmentation {
+    fn find_split_point(&self, chars: &[char], start: usize, target: usize) -> Option<usize> {
+        let fullwidth_endings = ['。', '！', '？', '、', '；', '：'];
+        for i in (start..=target).rev() {
+            if i > start && fullwidth_endings.contains(&chars[i - 1]) {
+                return Some(i);
+            }
         }
-        
-        let mut result = String::new();
-        
-        for (i, chunk) in chunks.iter().enumerate() {
-            if i == 0 {
-                result.push_str(&chunk.translated_text);
-            } else {
-                // Remove overlap from the beginning of continuation chunks
-                let text = if chunk.overlap_start > 0 {
-                    // Try to intelligently remove the overlap
-                    self.remove_overlap(
-                        &chunks[i - 1].translated_text,
-                        &chunk.translated_text,
-                        chunk.overlap_start
-                    )
-                } else {
-                    chunk.translated_text.clone()
-                };
-                
-                // Add appropriate spacing
-                if !result.ends_with(char::is_whitespace) && !text.starts_with(char::is_whitespace) {
-                    result.push(' ');
-                }
-                
-                result.push_str(&text);
+
+        for i in (start..=target).rev() {
+            if i > start && Self::is_cjk_ideograph(chars[i - 1]) {
+                return Some(i);
             }
         }
-        
-        result
+
+        None
+    }
+}
+
+/// Wraps another strategy and rejects any candidate boundary that looks like
+/// it falls right after an abbreviation (`Dr.`, `e.g.`, a single uppercase
+/// initial) rather than a genuine sentence end, retrying earlier in the
+/// range until one survives or the inner strategy runs out of candidates.
+///
+/// A candidate is treated as an abbreviation when the token before the `.`
+/// is three letters or fewer and the next word isn't capitalized - a
+/// lowercase continuation (`e.g. the cat`) is the clearest sign the `.`
+/// didn't end a sentence.
+pub struct AbbreviationGuard<S> {
+    inner: S,
+}
+
+impl<S> AbbreviationGuard<S> {
+    pub fn new(inner: S) -> Self {
+        Self { inner }
     }
-    
-    /// Remove overlapping content from the beginning of a translated chunk
-    fn remove_overlap(&self, previous: &str, current: &str, overlap_chars: usize) -> String {
-        // This is a simplified implementation
-        // In production, you might want to use more sophisticated matching
-        let chars: Vec<char> = current.chars().collect();
-        if chars.len() > overlap_chars {
-            chars[overlap_chars..].iter().collect()
-        } else {
-            current.to_string()
+
+    fn looks_like_abbreviation(chars: &[char], split_at: usize) -> bool {
+        if split_at == 0 || chars[split_at - 1] != '.' {
+            return false;
+        }
+
+        let mut token_start = split_at - 1;
+        while token_start > 0 && chars[token_start - 1].is_alphabetic() {
+            token_start -= 1;
+        }
+        let token_len = (split_at - 1) - token_start;
+        if token_len == 0 || token_len > 3 {
+            return false;
+        }
+
+        let next_word_start = chars[split_at..].iter().find(|c| !c.is_whitespace());
+        matches!(next_word_start, Some(c) if !c.is_uppercase())
+    }
+}
+
+impl<S: SegmentationStrategy> SegmentationStrategy for AbbreviationGuard<S> {
+    fn find_split_point(&self, chars: &[char], start: usize, target: usize) -> Option<usize> {
+        let mut search_end = target;
+        loop {
+            let candidate = self.inner.find_split_point(chars, start, search_end)?;
+            if !Self::looks_like_abbreviation(chars, candidate) {
+                return Some(candidate);
+            }
+            if candidate <= start + 1 {
+                return None;
+            }
+            search_end = candidate - 1;
         }
     }
 }
@@ -183,40 +344,40 @@ mod tests {
         let splitter = TextSplitter::new(1000);
         let text = "Hello, world!";
         let chunks = splitter.split_for_translation(text);
-        
+
         assert_eq!(chunks.len(), 1);
         assert_eq!(chunks[0].text, text);
         assert_eq!(chunks[0].index, 0);
         assert!(!chunks[0].is_continuation);
     }
-    
+
     #[test]
     fn test_split_at_sentence_boundary() {
         let splitter = TextSplitter::new(50);
         let text = "This is the first sentence. This is the second sentence. This is the third.";
         let chunks = splitter.split_for_translation(text);
-        
+
         assert!(chunks.len() > 1);
         // First chunk should end at a sentence boundary
         assert!(chunks[0].text.ends_with('.') || chunks[0].text.ends_with(". "));
     }
-    
+
     #[test]
     fn test_split_with_overlap() {
         let splitter = TextSplitter::new(100);
         let text = "A".repeat(250); // Long text that needs splitting
         let chunks = splitter.split_for_translation(&text);
-        
+
         assert_eq!(chunks.len(), 3);
         assert!(chunks[1].is_continuation);
         assert!(chunks[2].is_continuation);
         assert_eq!(chunks[1].overlap_start, 50);
     }
-    
+
     #[test]
     fn test_merge_translations() {
         let splitter = TextSplitter::new(100);
-        
+
         let translated_chunks = vec![
             TranslatedChunk {
                 index: 0,
@@ -229,20 +390,122 @@ mod tests {
                 overlap_start: 7,
             },
         ];
-        
+
         let merged = splitter.merge_translations(translated_chunks);
         assert!(merged.contains("Первая часть текста."));
         assert!(merged.contains("Вторая часть текста."));
         // Should not duplicate "текста."
         assert_eq!(merged.matches("текста.").count(), 2);
     }
-    
+
+    #[test]
+    fn test_merge_translations_reconciles_diverging_wording() {
+        let splitter = TextSplitter::new(100);
+
+        // The two chunks were translated independently, so the overlap
+        // isn't a clean prefix match: the continuation repeats "the cat sat"
+        // but spells it with different trailing punctuation/casing.
+        let translated_chunks = vec![
+            TranslatedChunk {
+                index: 0,
+                translated_text: "The cat sat on the mat".to_string(),
+                overlap_start: 0,
+            },
+            TranslatedChunk {
+                index: 1,
+                translated_text: "the cat sat on the mat, and then it slept.".to_string(),
+                overlap_start: 10,
+            },
+        ];
+
+        let merged = splitter.merge_translations(translated_chunks);
+        assert_eq!(merged.matches("cat sat").count(), 1);
+        assert!(merged.ends_with("and then it slept."));
+    }
+
+    #[test]
+    fn test_merge_translations_keeps_chunk_whole_when_no_overlap_found() {
+        let splitter = TextSplitter::new(100);
+
+        let translated_chunks = vec![
+            TranslatedChunk {
+                index: 0,
+                translated_text: "Completely different opening.".to_string(),
+                overlap_start: 0,
+            },
+            TranslatedChunk {
+                index: 1,
+                translated_text: "Unrelated continuation text.".to_string(),
+                overlap_start: 10,
+            },
+        ];
+
+        let merged = splitter.merge_translations(translated_chunks);
+        assert!(merged.contains("Completely different opening."));
+        assert!(merged.contains("Unrelated continuation text."));
+    }
+
+    #[test]
+    fn test_cjk_segmentation_prefers_fullwidth_punctuation() {
+        let strategy = CjkSegmentation;
+        let text = "你好世界。再见世界";
+        let chars: Vec<char> = text.chars().collect();
+        let split = strategy.find_split_point(&chars, 0, chars.len() - 1).unwrap();
+        assert_eq!(chars[split - 1], '。');
+    }
+
+    #[test]
+    fn test_cjk_segmentation_falls_back_to_any_ideograph_without_punctuation() {
+        let strategy = CjkSegmentation;
+        let text = "你好世界再见世界";
+        let chars: Vec<char> = text.chars().collect();
+        let split = strategy.find_split_point(&chars, 0, chars.len() - 1).unwrap();
+        assert!(split > 0 && split < chars.len());
+    }
+
+    #[test]
+    fn test_abbreviation_guard_skips_split_after_lowercase_continuation() {
+        let guard = AbbreviationGuard::new(DefaultSegmentation);
+        let text = "See e.g. the appendix for details.";
+        let chars: Vec<char> = text.chars().collect();
+        // Target right after "e.g." so the unguarded default would stop there.
+        let target = text.find("e.g.").unwrap() + "e.g.".chars().count();
+        let split = guard.find_split_point(&chars, 0, target);
+        // The default strategy alone would stop right after "e.g." - the
+        // guard should reject that and keep searching earlier in the range.
+        assert_ne!(split, Some(target));
+    }
+
+    #[test]
+    fn test_abbreviation_guard_allows_split_before_capitalized_word() {
+        let guard = AbbreviationGuard::new(DefaultSegmentation);
+        let text = "This is a sentence. Another one follows.";
+        let chars: Vec<char> = text.chars().collect();
+        let target = chars.len() - 1;
+        let split = guard.find_split_point(&chars, 0, target).unwrap();
+        assert_eq!(chars[split - 1], '.');
+        assert_eq!(chars[split], ' ');
+    }
+
+    #[test]
+    fn test_split_for_translation_with_cjk_strategy() {
+        let splitter = TextSplitter::new(5);
+        let text = "你好世界再见世界你好世界";
+        let chunks = splitter.split_for_translation_with(text, &CjkSegmentation);
+
+        assert!(chunks.len() > 1);
+        for chunk in &chunks {
+            assert!(chunk.text.is_char_boundary(0));
+            assert!(chunk.text.is_char_boundary(chunk.text.len()));
+        }
+    }
+
     #[test]
     fn test_unicode_splitting() {
         let splitter = TextSplitter::new(10);
         let text = "Привет, мир! Как дела? 你好世界！";
         let chunks = splitter.split_for_translation(text);
-        
+
         // Should split but preserve complete characters
         assert!(chunks.len() > 1);
         for chunk in chunks {
@@ -251,4 +514,4 @@ mod tests {
             assert!(chunk.text.is_char_boundary(chunk.text.len()));
         }
     }
-}
\ No newline at end of file
+}
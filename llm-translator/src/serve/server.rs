@@ -0,0 +1,156 @@
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::sse::{Event, KeepAlive, Sse},
+    response::IntoResponse,
+    routing::post,
+    Json, Router,
+};
+use futures_util::stream::Stream;
+use futures_util::StreamExt;
+use thiserror::Error;
+use tokio::net::TcpListener;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::UnboundedReceiverStream;
+use tracing::{error, info};
+
+use crate::llm::{
+    ChatChunkChoice, ChatChunkDelta, ChatCompletionChunk, ChatCompletionRequest, CompletionProvider, LlmError,
+    ReplyHandler,
+};
+
+#[derive(Error, Debug)]
+pub enum ServeError {
+    #[error("Failed to bind to {addr}: {source}")]
+    BindError { addr: SocketAddr, source: std::io::Error },
+
+    #[error("Server error: {0}")]
+    RuntimeError(#[from] std::io::Error),
+}
+
+#[derive(Debug, Clone)]
+pub struct ServeConfig {
+    pub bind_addr: SocketAddr,
+}
+
+impl Default for ServeConfig {
+    fn default() -> Self {
+        Self {
+            bind_addr: SocketAddr::from(([127, 0, 0, 1], 8787)),
+        }
+    }
+}
+
+#[derive(Clone)]
+struct AppState {
+    provider: Arc<dyn CompletionProvider>,
+}
+
+/// Bind a TCP listener and serve an OpenAI-compatible `/v1/chat/completions`
+/// endpoint, proxying requests through `provider`. Runs until Ctrl-C.
+pub async fn serve(provider: Arc<dyn CompletionProvider>, config: ServeConfig) -> Result<(), ServeError> {
+    let state = AppState { provider };
+    let app = Router::new()
+        .route("/v1/chat/completions", post(chat_completions))
+        .with_state(state);
+
+    let listener = TcpListener::bind(config.bind_addr)
+        .await
+        .map_err(|source| ServeError::BindError { addr: config.bind_addr, source })?;
+
+    info!("Embedded LLM server listening on {}", config.bind_addr);
+
+    axum::serve(listener, app)
+        .with_graceful_shutdown(shutdown_signal())
+        .await?;
+
+    Ok(())
+}
+
+async fn shutdown_signal() {
+    if let Err(e) = tokio::signal::ctrl_c().await {
+        error!("Failed to listen for shutdown signal: {}", e);
+    }
+    info!("Embedded LLM server shutting down");
+}
+
+async fn chat_completions(
+    State(state): State<AppState>,
+    Json(request): Json<ChatCompletionRequest>,
+) -> axum::response::Response {
+    if request.stream.unwrap_or(false) {
+        stream_completion(state, request).into_response()
+    } else {
+        match state.provider.send(request, None).await {
+            Ok(response) => Json(response).into_response(),
+            Err(e) => error_response(e),
+        }
+    }
+}
+
+fn error_response(error: LlmError) -> axum::response::Response {
+    (StatusCode::BAD_GATEWAY, error.to_string()).into_response()
+}
+
+/// Forward `ReplyHandler::text` calls onto an unbounded channel so the
+/// streaming provider call and the SSE response can run concurrently.
+struct ChannelReplyHandler {
+    sender: mpsc::UnboundedSender<String>,
+}
+
+impl ReplyHandler for ChannelReplyHandler {
+    fn text(&mut self, delta: &str) {
+        let _ = self.sender.send(delta.to_string());
+    }
+}
+
+fn stream_completion(
+    state: AppState,
+    request: ChatCompletionRequest,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let (tx, rx) = mpsc::unbounded_channel::<String>();
+    let model = request.model.clone();
+
+    tokio::spawn(async move {
+        let mut handler = ChannelReplyHandler { sender: tx };
+        if let Err(e) = state.provider.send_stream(request, &mut handler, None).await {
+            error!("Streaming completion failed: {}", e);
+        }
+    });
+
+    let stream = UnboundedReceiverStream::new(rx).map(move |delta| {
+        let chunk = ChatCompletionChunk {
+            id: format!("chatcmpl-{}", uuid::Uuid::new_v4()),
+            object: "chat.completion.chunk".to_string(),
+            created: 0,
+            model: model.clone(),
+            choices: vec![ChatChunkChoice {
+                index: 0,
+                delta: ChatChunkDelta { role: None, content: Some(delta) },
+                finish_reason: None,
+            }],
+        };
+
+        Ok(Event::default().data(serde_json::to_string(&chunk).unwrap_or_default()))
+    });
+
+    let done = tokio_stream::once(Ok(Event::default().data("[DONE]")));
+
+    Sse::new(stream.chain(done)).keep_alive(KeepAlive::default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_bind_addr() {
+        let config = ServeConfig::default();
+        assert_eq!(config.bind_addr.port(), 8787);
+        assert!(config.bind_addr.ip().is_loopback());
+    }
+}
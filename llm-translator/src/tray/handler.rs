@@ -1,21 +1,96 @@
-use std::sync::Arc;
+use std::sync::{Arc, Mutex as StdMutex};
 use tauri::{
     AppHandle, Manager, Runtime,
     tray::{TrayIcon, TrayIconBuilder, TrayIconEvent},
     menu::MenuEvent,
 };
-use tokio::sync::{mpsc, RwLock};
+use tokio::sync::{mpsc, Notify, RwLock};
+use tokio::time::Duration;
 use tracing::{debug, error, info};
 
-use super::icon::{TrayIconState, TrayIconGenerator};
+use super::icon::{TrayIconState, TrayIconGenerator, LOADING_FRAME_COUNT};
 use super::menu::TrayMenuBuilder;
 use crate::config::Config;
+use crate::hotkeys::Trigger;
+
+/// Interval between loading-spinner frames.
+const SPINNER_FRAME_INTERVAL: Duration = Duration::from_millis(150);
+/// How long the success flash holds before settling back to `Active`.
+const DRAIN_FLASH_DURATION: Duration = Duration::from_millis(400);
+
+struct JobState {
+    next_id: u64,
+    jobs: Vec<(u64, String)>,
+    error_seq: u64,
+    last_error: Option<(u64, String, String)>,
+}
+
+/// Shared state behind every [`OperationGuard`] handed out by
+/// [`TrayHandler::begin_operation`], tracking which jobs are in flight so
+/// concurrent callers compose instead of overwriting each other's tray state.
+struct OperationTracker {
+    state: StdMutex<JobState>,
+    notify: Notify,
+}
+
+impl OperationTracker {
+    fn new() -> Self {
+        Self {
+            state: StdMutex::new(JobState {
+                next_id: 0,
+                jobs: Vec::new(),
+                error_seq: 0,
+                last_error: None,
+            }),
+            notify: Notify::new(),
+        }
+    }
+}
+
+/// Handle for one in-flight operation, obtained from
+/// [`TrayHandler::begin_operation`]. Dropping it (on any code path, including
+/// an early return or panic unwind) marks the operation complete; call
+/// [`OperationGuard::fail`] first to surface an error for this job's label
+/// instead of a silent completion.
+pub struct OperationGuard {
+    tracker: Arc<OperationTracker>,
+    id: u64,
+}
+
+impl OperationGuard {
+    /// Report that this operation failed. The tray surfaces a notification
+    /// naming this job's label, and the tooltip reflects the error until the
+    /// next state change.
+    pub fn fail(&self, message: impl Into<String>) {
+        let mut state = self.tracker.state.lock().unwrap();
+        let label = state
+            .jobs
+            .iter()
+            .find(|(id, _)| *id == self.id)
+            .map(|(_, label)| label.clone())
+            .unwrap_or_default();
+        state.error_seq += 1;
+        state.last_error = Some((state.error_seq, label, message.into()));
+        drop(state);
+        self.tracker.notify.notify_one();
+    }
+}
+
+impl Drop for OperationGuard {
+    fn drop(&mut self) {
+        let mut state = self.tracker.state.lock().unwrap();
+        state.jobs.retain(|(id, _)| *id != self.id);
+        drop(state);
+        self.tracker.notify.notify_one();
+    }
+}
 
 #[derive(Debug, Clone)]
 pub enum TrayEvent {
     Toggle,
     OpenSettings,
     ChangeStyle(String),
+    SetTriggerMode(Trigger),
     ResetStatistics,
     ShowHelp(String),
     Quit,
@@ -27,6 +102,7 @@ pub struct TrayHandler<R: Runtime> {
     config: Arc<RwLock<Config>>,
     event_sender: mpsc::Sender<TrayEvent>,
     current_state: Arc<RwLock<TrayIconState>>,
+    tracker: Arc<OperationTracker>,
 }
 
 impl<R: Runtime> TrayHandler<R> {
@@ -48,16 +124,136 @@ impl<R: Runtime> TrayHandler<R> {
             })
             .build(&app)?;
         
+        let current_state = Arc::new(RwLock::new(TrayIconState::Active));
+        let tracker = Arc::new(OperationTracker::new());
+
+        Self::spawn_animator(app.clone(), tray.clone(), Arc::clone(&tracker), Arc::clone(&current_state));
+
         Ok(Self {
             app,
             tray,
             config,
             event_sender,
-            current_state: Arc::new(RwLock::new(TrayIconState::Active)),
+            current_state,
+            tracker,
         })
     }
-    
-    /// Update tray icon state
+
+    /// Begin tracking a concurrent operation under `label`. While the guard
+    /// (or any sibling guard) is alive, the tray animates the loading icon
+    /// and the tooltip aggregates progress across every in-flight job. The
+    /// operation is marked complete implicitly when the guard is dropped;
+    /// call [`OperationGuard::fail`] beforehand to report an error instead.
+    pub fn begin_operation(&self, label: impl Into<String>) -> OperationGuard {
+        let mut state = self.tracker.state.lock().unwrap();
+        state.next_id += 1;
+        let id = state.next_id;
+        state.jobs.push((id, label.into()));
+        drop(state);
+        self.tracker.notify.notify_one();
+
+        OperationGuard {
+            tracker: Arc::clone(&self.tracker),
+            id,
+        }
+    }
+
+    /// Background task that renders the loading spinner while any operation
+    /// is in flight, aggregates the tooltip across concurrent jobs, surfaces
+    /// job failures as notifications, and reverts to `Active` - flashing
+    /// once - when the queue drains to empty.
+    fn spawn_animator(
+        app: AppHandle<R>,
+        tray: TrayIcon<R>,
+        tracker: Arc<OperationTracker>,
+        current_state: Arc<RwLock<TrayIconState>>,
+    ) {
+        tauri::async_runtime::spawn(async move {
+            let mut frame: u8 = 0;
+            let mut batch_total: usize = 0;
+            let mut notified_error_seq: u64 = 0;
+            let mut was_active = false;
+
+            loop {
+                let (jobs, last_error) = {
+                    let state = tracker.state.lock().unwrap();
+                    (state.jobs.clone(), state.last_error.clone())
+                };
+
+                if let Some((seq, label, message)) = &last_error {
+                    if *seq > notified_error_seq {
+                        notified_error_seq = *seq;
+                        let _ = app
+                            .notification()
+                            .builder()
+                            .title("Translation failed")
+                            .body(format!("{}: {}", label, message))
+                            .show();
+                    }
+                }
+
+                if jobs.is_empty() {
+                    batch_total = 0;
+
+                    if was_active {
+                        was_active = false;
+                        // Flash once on drain-to-empty, not per completed job.
+                        let _ = tray.set_icon(Some(TrayIconGenerator::success_icon()));
+                        let _ = tray.set_tooltip(Some(TrayIconState::Success.tooltip()));
+                        tokio::time::sleep(DRAIN_FLASH_DURATION).await;
+
+                        *current_state.write().await = TrayIconState::Active;
+                        let _ = tray.set_icon(Some(TrayIconState::Active.to_icon()));
+                        let _ = tray.set_tooltip(Some(TrayIconState::Active.tooltip()));
+                        let _ = TrayMenuBuilder::update_status(&app, "Status: Active", true);
+                    }
+
+                    // The error (if any) has already been surfaced as a
+                    // notification above - clear it now that its batch has
+                    // drained, so it doesn't linger and get misattributed to
+                    // a later, unrelated job's tooltip.
+                    if last_error.is_some() {
+                        tracker.state.lock().unwrap().last_error = None;
+                    }
+
+                    tracker.notify.notified().await;
+                    continue;
+                }
+
+                was_active = true;
+                batch_total = batch_total.max(jobs.len());
+                let completed = batch_total.saturating_sub(jobs.len());
+
+                let tooltip = if let Some((_, label, message)) = &last_error {
+                    format!("LLM Translator - {} failed: {}", label, message)
+                } else if jobs.len() == 1 {
+                    format!("LLM Translator - Translating: {}", jobs[0].1)
+                } else {
+                    format!("LLM Translator - Translating {} of {}…", completed + 1, batch_total)
+                };
+
+                *current_state.write().await = TrayIconState::Loading;
+                let _ = tray.set_icon(Some(TrayIconGenerator::loading_icon_frame(frame)));
+                let _ = tray.set_tooltip(Some(&tooltip));
+                let _ = TrayMenuBuilder::update_status(&app, "Status: Processing...", true);
+
+                frame = (frame + 1) % LOADING_FRAME_COUNT;
+
+                tokio::select! {
+                    _ = tokio::time::sleep(SPINNER_FRAME_INTERVAL) => {}
+                    _ = tracker.notify.notified() => {}
+                }
+            }
+        });
+    }
+
+    /// Set the tray icon state directly - for standalone states like
+    /// `Inactive` that aren't tied to an in-flight job. Concurrent
+    /// translations should go through [`TrayHandler::begin_operation`]
+    /// instead: the animator task owns `Loading`/`Active` while any
+    /// operation is running and will overwrite a state set here on its next
+    /// tick, so individual jobs compose through it rather than overwriting
+    /// each other.
     pub async fn set_state(&self, state: TrayIconState) -> tauri::Result<()> {
         let mut current = self.current_state.write().await;
         if *current != state {
@@ -101,7 +297,12 @@ impl<R: Runtime> TrayHandler<R> {
     pub async fn set_active_style(&self, style_id: &str) -> tauri::Result<()> {
         TrayMenuBuilder::set_active_style(&self.app, style_id)
     }
-    
+
+    /// Set the active trigger mode shown in the "Trigger Mode" menu.
+    pub async fn set_trigger_mode(&self, trigger: Trigger) -> tauri::Result<()> {
+        TrayMenuBuilder::set_trigger_mode(&self.app, trigger)
+    }
+
     /// Handle menu events
     fn handle_menu_event(app: &AppHandle<R>, event: MenuEvent) {
         let event_id = event.id().0.as_str();
@@ -117,6 +318,8 @@ impl<R: Runtime> TrayHandler<R> {
                 "style_formal" => Some(TrayEvent::ChangeStyle("formal".to_string())),
                 "style_academic" => Some(TrayEvent::ChangeStyle("academic".to_string())),
                 "style_creative" => Some(TrayEvent::ChangeStyle("creative".to_string())),
+                "trigger_press" => Some(TrayEvent::SetTriggerMode(Trigger::KeyPressed)),
+                "trigger_release" => Some(TrayEvent::SetTriggerMode(Trigger::KeyReleased)),
                 "stats_reset" => Some(TrayEvent::ResetStatistics),
                 "help_docs" => Some(TrayEvent::ShowHelp("docs".to_string())),
                 "help_shortcuts" => Some(TrayEvent::ShowHelp("shortcuts".to_string())),
@@ -198,6 +401,7 @@ mod tests {
             TrayEvent::Toggle,
             TrayEvent::OpenSettings,
             TrayEvent::ChangeStyle("general".to_string()),
+            TrayEvent::SetTriggerMode(Trigger::KeyReleased),
             TrayEvent::ResetStatistics,
             TrayEvent::ShowHelp("docs".to_string()),
             TrayEvent::Quit,
@@ -208,4 +412,60 @@ mod tests {
             let _cloned = event.clone();
         }
     }
+
+    fn begin(tracker: &Arc<OperationTracker>, label: &str) -> OperationGuard {
+        let mut state = tracker.state.lock().unwrap();
+        state.next_id += 1;
+        let id = state.next_id;
+        state.jobs.push((id, label.to_string()));
+        drop(state);
+        tracker.notify.notify_one();
+        OperationGuard {
+            tracker: Arc::clone(tracker),
+            id,
+        }
+    }
+
+    #[test]
+    fn test_operation_guard_tracks_concurrent_jobs() {
+        let tracker = Arc::new(OperationTracker::new());
+
+        let first = begin(&tracker, "translating A");
+        assert_eq!(tracker.state.lock().unwrap().jobs.len(), 1);
+
+        let second = begin(&tracker, "translating B");
+        assert_eq!(tracker.state.lock().unwrap().jobs.len(), 2);
+
+        drop(first);
+        assert_eq!(tracker.state.lock().unwrap().jobs.len(), 1);
+
+        drop(second);
+        assert_eq!(tracker.state.lock().unwrap().jobs.len(), 0);
+    }
+
+    #[test]
+    fn test_operation_guard_fail_records_label_and_message() {
+        let tracker = Arc::new(OperationTracker::new());
+        let job = begin(&tracker, "translating C");
+
+        job.fail("rate limited");
+
+        let state = tracker.state.lock().unwrap();
+        let (seq, label, message) = state.last_error.as_ref().expect("expected a recorded error");
+        assert_eq!(*seq, 1);
+        assert_eq!(label, "translating C");
+        assert_eq!(message, "rate limited");
+    }
+
+    #[test]
+    fn test_operation_guard_drop_completes_even_after_fail() {
+        let tracker = Arc::new(OperationTracker::new());
+        let job = begin(&tracker, "translating D");
+        job.fail("boom");
+        drop(job);
+
+        // The job still completes on drop even though it reported failure -
+        // the error is surfaced separately, not by withholding completion.
+        assert!(tracker.state.lock().unwrap().jobs.is_empty());
+    }
 }
\ No newline at end of file
@@ -1,5 +1,8 @@
 use tauri::{Icon, Runtime};
 
+/// Number of rotation positions in the loading spinner animation.
+pub const LOADING_FRAME_COUNT: u8 = 8;
+
 /// Generate tray icon based on current state
 pub struct TrayIconGenerator;
 
@@ -20,6 +23,52 @@ impl TrayIconGenerator {
     pub fn loading_icon() -> Icon {
         Self::create_icon(&[255, 152, 0, 255]) // Orange color
     }
+
+    /// One frame of the loading spinner: the loading circle with a brighter
+    /// wedge rotated to `frame`, cycling through [`LOADING_FRAME_COUNT`]
+    /// positions as an operation is in flight.
+    pub fn loading_icon_frame(frame: u8) -> Icon {
+        let base = [255, 152, 0, 255]; // Orange
+        let highlight = [255, 213, 79, 255]; // Bright amber
+        let angle = (frame as f32 % LOADING_FRAME_COUNT as f32) / LOADING_FRAME_COUNT as f32
+            * std::f32::consts::TAU;
+
+        let size = 32;
+        let mut rgba = Vec::with_capacity((size * size * 4) as usize);
+
+        for y in 0..size {
+            for x in 0..size {
+                let dx = x as f32 - size as f32 / 2.0;
+                let dy = y as f32 - size as f32 / 2.0;
+                let distance = (dx * dx + dy * dy).sqrt();
+
+                if distance < size as f32 / 2.0 - 2.0 {
+                    let point_angle = dy.atan2(dx).rem_euclid(std::f32::consts::TAU);
+                    let delta = (point_angle - angle).rem_euclid(std::f32::consts::TAU);
+                    let color = if delta < std::f32::consts::FRAC_PI_4 {
+                        highlight
+                    } else {
+                        base
+                    };
+                    rgba.extend_from_slice(&color);
+                } else if distance < size as f32 / 2.0 {
+                    let alpha = ((size as f32 / 2.0 - distance) * 255.0) as u8;
+                    rgba.push(base[0]);
+                    rgba.push(base[1]);
+                    rgba.push(base[2]);
+                    rgba.push(alpha);
+                } else {
+                    rgba.extend_from_slice(&[0, 0, 0, 0]);
+                }
+            }
+        }
+
+        Icon::Rgba {
+            rgba,
+            width: size,
+            height: size,
+        }
+    }
     
     /// Create error icon (red)
     pub fn error_icon() -> Icon {
@@ -117,6 +166,18 @@ mod tests {
         }
     }
     
+    #[test]
+    fn test_loading_icon_frame_rotates() {
+        let Icon::Rgba { rgba: frame0, .. } = TrayIconGenerator::loading_icon_frame(0) else {
+            panic!("Expected RGBA icon");
+        };
+        let Icon::Rgba { rgba: frame1, .. } = TrayIconGenerator::loading_icon_frame(1) else {
+            panic!("Expected RGBA icon");
+        };
+        assert_eq!(frame0.len(), (32 * 32 * 4) as usize);
+        assert_ne!(frame0, frame1);
+    }
+
     #[test]
     fn test_icon_states() {
         let states = vec![
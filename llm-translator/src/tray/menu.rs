@@ -1,8 +1,22 @@
 use tauri::{
-    menu::{Menu, MenuItem, PredefinedMenuItem, Submenu},
+    menu::{CheckMenuItem, Menu, MenuItem, PredefinedMenuItem, Submenu},
     AppHandle, Manager, Runtime,
 };
 
+use crate::hotkeys::Trigger;
+
+/// Ids of the "Translation Style" submenu's radio group, in menu order.
+const STYLE_IDS: [&str; 5] = [
+    "style_general",
+    "style_twitter",
+    "style_formal",
+    "style_academic",
+    "style_creative",
+];
+
+/// Ids of the "Trigger Mode" submenu's radio group, in menu order.
+const TRIGGER_MODE_IDS: [&str; 2] = ["trigger_press", "trigger_release"];
+
 #[derive(Clone)]
 pub struct TrayMenuBuilder;
 
@@ -16,20 +30,33 @@ impl TrayMenuBuilder {
         // Separator
         let separator1 = PredefinedMenuItem::separator(app)?;
         
-        // Translation settings submenu
+        // Translation settings submenu - a mutually exclusive radio group,
+        // "General" checked by default.
         let translation_menu = Submenu::with_items(
             app,
             "Translation Style",
             true,
             &[
-                &MenuItem::with_id(app, "style_general", "General", true, None::<&str>)?,
-                &MenuItem::with_id(app, "style_twitter", "Twitter", true, None::<&str>)?,
-                &MenuItem::with_id(app, "style_formal", "Formal", true, None::<&str>)?,
-                &MenuItem::with_id(app, "style_academic", "Academic", true, None::<&str>)?,
-                &MenuItem::with_id(app, "style_creative", "Creative", true, None::<&str>)?,
+                &CheckMenuItem::with_id(app, "style_general", "General", true, true, None::<&str>)?,
+                &CheckMenuItem::with_id(app, "style_twitter", "Twitter", true, false, None::<&str>)?,
+                &CheckMenuItem::with_id(app, "style_formal", "Formal", true, false, None::<&str>)?,
+                &CheckMenuItem::with_id(app, "style_academic", "Academic", true, false, None::<&str>)?,
+                &CheckMenuItem::with_id(app, "style_creative", "Creative", true, false, None::<&str>)?,
             ],
         )?;
-        
+
+        // Trigger mode submenu - another radio group, "Key Press" checked by
+        // default, matching `Trigger::default()`.
+        let trigger_mode_menu = Submenu::with_items(
+            app,
+            "Trigger Mode",
+            true,
+            &[
+                &CheckMenuItem::with_id(app, "trigger_press", "Key Press", true, true, None::<&str>)?,
+                &CheckMenuItem::with_id(app, "trigger_release", "Key Release", true, false, None::<&str>)?,
+            ],
+        )?;
+
         // Main actions
         let toggle = MenuItem::with_id(app, "toggle", "Disable Translation", true, None::<&str>)?;
         let settings = MenuItem::with_id(app, "settings", "Settings...", true, None::<&str>)?;
@@ -71,6 +98,7 @@ impl TrayMenuBuilder {
         menu.append(&status)?;
         menu.append(&separator1)?;
         menu.append(&translation_menu)?;
+        menu.append(&trigger_mode_menu)?;
         menu.append(&toggle)?;
         menu.append(&settings)?;
         menu.append(&separator2)?;
@@ -120,28 +148,44 @@ impl TrayMenuBuilder {
         Ok(())
     }
     
-    pub fn set_active_style<R: Runtime>(
+    /// Check `active_id` and uncheck every other id in `group`, so `group`
+    /// behaves as a mutually exclusive radio group. Shared by every toggle
+    /// group in the menu (translation style, trigger mode, and any future
+    /// one, e.g. target language) instead of each growing its own
+    /// checkmark bookkeeping.
+    fn set_exclusive_check<R: Runtime>(
         app: &AppHandle<R>,
-        style_id: &str,
+        group: &[&str],
+        active_id: &str,
     ) -> tauri::Result<()> {
-        // Reset all style checkmarks
-        let styles = ["style_general", "style_twitter", "style_formal", "style_academic", "style_creative"];
-        
-        for style in &styles {
-            if let Some(item) = app.menu().get(style) {
-                let is_active = *style == style_id;
-                // In Tauri 2.0, we would use set_checked if available
-                // For now, we'll update the text to indicate selection
-                let menu_item = item.as_menuitem().unwrap();
-                let text = menu_item.text()?;
-                if is_active && !text.starts_with("✓ ") {
-                    menu_item.set_text(&format!("✓ {}", text))?;
-                } else if !is_active && text.starts_with("✓ ") {
-                    menu_item.set_text(&text[2..])?;
+        for id in group {
+            if let Some(item) = app.menu().get(id) {
+                if let Some(check_item) = item.as_check_menuitem() {
+                    check_item.set_checked(*id == active_id)?;
                 }
             }
         }
-        
+
         Ok(())
     }
+
+    /// Set the active translation style in the "Translation Style" menu.
+    pub fn set_active_style<R: Runtime>(
+        app: &AppHandle<R>,
+        style_id: &str,
+    ) -> tauri::Result<()> {
+        Self::set_exclusive_check(app, &STYLE_IDS, style_id)
+    }
+
+    /// Set the active trigger mode in the "Trigger Mode" menu.
+    pub fn set_trigger_mode<R: Runtime>(
+        app: &AppHandle<R>,
+        trigger: Trigger,
+    ) -> tauri::Result<()> {
+        let active_id = match trigger {
+            Trigger::KeyPressed => "trigger_press",
+            Trigger::KeyReleased => "trigger_release",
+        };
+        Self::set_exclusive_check(app, &TRIGGER_MODE_IDS, active_id)
+    }
 }
\ No newline at end of file
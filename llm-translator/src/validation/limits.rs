@@ -1,165 +1,383 @@
-use std::collections::VecDeque;
+use std::collections::{HashMap, VecDeque};
 use std::time::{Duration, Instant};
-use chrono::{DateTime, Utc, Datelike};
+use chrono::{DateTime, Utc};
+use reqwest::header::HeaderMap;
 use thiserror::Error;
 use std::sync::{Arc, Mutex};
-use std::sync::atomic::{AtomicUsize, Ordering};
 
 #[derive(Error, Debug)]
 pub enum RateLimitError {
     #[error("Rate limit exceeded: please wait {wait_time:?}")]
     MinuteLimit { wait_time: Duration },
-    
+
     #[error("Daily limit exceeded: {used}/{max} requests used today")]
     DailyLimit { used: usize, max: usize },
+
+    #[error("Rate limit exceeded on bucket '{bucket}': please wait {wait_time:?}")]
+    BucketLimit { bucket: String, wait_time: Duration },
+
+    #[error("Server-imposed rate limit freeze active, please wait {remaining:?}")]
+    Frozen { remaining: Duration },
+}
+
+/// A server-imposed "frozen until" signal shared between `LlmClient` and
+/// `RateLimiter`, so a 429's `Retry-After` collapses every caller's backoff
+/// into one coordinated pause instead of each one independently retrying.
+#[derive(Debug, Clone)]
+pub struct FreezeGate {
+    until: Arc<Mutex<Option<Instant>>>,
+}
+
+impl FreezeGate {
+    pub fn new() -> Self {
+        Self {
+            until: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Record a freeze window, extending rather than shortening any freeze
+    /// already in effect.
+    pub fn freeze_until(&self, until: Instant) {
+        if let Ok(mut guard) = self.until.lock() {
+            let should_extend = match *guard {
+                Some(existing) => until > existing,
+                None => true,
+            };
+
+            if should_extend {
+                *guard = Some(until);
+            }
+        }
+    }
+
+    /// Time remaining on the freeze, or `None` if it has expired or was never set.
+    pub fn remaining(&self) -> Option<Duration> {
+        let mut guard = self.until.lock().ok()?;
+        let until = (*guard)?;
+        let now = Instant::now();
+
+        if now >= until {
+            *guard = None;
+            return None;
+        }
+
+        Some(until - now)
+    }
+}
+
+impl Default for FreezeGate {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// One named sliding-window limit: at most `max` cost units consumed within
+/// `window`. Several of these are checked together so e.g. a per-minute
+/// request cap and a per-day cap can coexist.
+#[derive(Debug, Clone)]
+pub struct BucketConfig {
+    pub name: String,
+    pub window: Duration,
+    pub max: usize,
+}
+
+impl BucketConfig {
+    pub fn new(name: impl Into<String>, window: Duration, max: usize) -> Self {
+        Self {
+            name: name.into(),
+            window,
+            max,
+        }
+    }
+}
+
+/// Live sliding-window state for one bucket, scoped to a single model.
+#[derive(Debug)]
+struct BucketWindow {
+    max: usize,
+    entries: VecDeque<(Instant, usize)>,
+    used: usize,
+}
+
+impl BucketWindow {
+    fn new(max: usize) -> Self {
+        Self {
+            max,
+            entries: VecDeque::new(),
+            used: 0,
+        }
+    }
+
+    fn evict(&mut self, now: Instant, window: Duration) {
+        while let Some(&(entered_at, cost)) = self.entries.front() {
+            if now.duration_since(entered_at) >= window {
+                self.entries.pop_front();
+                self.used = self.used.saturating_sub(cost);
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn wait_for_slot(&self, now: Instant, window: Duration) -> Option<Duration> {
+        self.entries.front().and_then(|&(entered_at, _)| {
+            let elapsed = now.duration_since(entered_at);
+            (elapsed < window).then(|| window - elapsed)
+        })
+    }
+}
+
+#[derive(Debug, Default)]
+struct ModelState {
+    buckets: HashMap<String, BucketWindow>,
 }
 
 // Internal state that needs synchronization
 #[derive(Debug)]
 struct RateLimiterState {
-    requests: VecDeque<Instant>,
-    daily_count: usize,
+    models: HashMap<String, ModelState>,
+    // Live ceilings keyed by bucket name, seeded from `BucketConfig::max` but
+    // overridable at runtime via `update_limits`/`sync_from_headers`.
+    ceilings: HashMap<String, usize>,
     last_reset: DateTime<Utc>,
 }
 
+/// Requests not tied to a specific model (the common case, and the only case
+/// reachable through the back-compat `new`/`check_and_update` pair) share this key.
+const DEFAULT_MODEL: &str = "__default__";
+
 #[derive(Debug, Clone)]
 pub struct RateLimiter {
     state: Arc<Mutex<RateLimiterState>>,
-    max_per_minute: AtomicUsize,
-    max_per_day: AtomicUsize,
+    bucket_configs: Arc<Vec<BucketConfig>>,
+    freeze: FreezeGate,
 }
 
 impl RateLimiter {
+    /// Thin wrapper over `with_buckets` for the common case: one "minute"
+    /// burst bucket plus one "day" bucket, matching the limiter's original shape.
     pub fn new(max_per_minute: usize, max_per_day: usize) -> Self {
-        let state = RateLimiterState {
-            requests: VecDeque::with_capacity(max_per_minute.min(1000)), // Cap capacity
-            daily_count: 0,
-            last_reset: Utc::now(),
-        };
-        
+        Self::with_buckets(vec![
+            BucketConfig::new("minute", Duration::from_secs(60), max_per_minute),
+            BucketConfig::new("day", Duration::from_secs(86_400), max_per_day),
+        ])
+    }
+
+    /// Build a limiter from an arbitrary set of named sliding-window buckets,
+    /// all of which must have room before a request is admitted.
+    pub fn with_buckets(bucket_configs: Vec<BucketConfig>) -> Self {
+        let ceilings = bucket_configs
+            .iter()
+            .map(|config| (config.name.clone(), config.max))
+            .collect();
+
         Self {
-            state: Arc::new(Mutex::new(state)),
-            max_per_minute: AtomicUsize::new(max_per_minute),
-            max_per_day: AtomicUsize::new(max_per_day),
+            state: Arc::new(Mutex::new(RateLimiterState {
+                models: HashMap::new(),
+                ceilings,
+                last_reset: Utc::now(),
+            })),
+            bucket_configs: Arc::new(bucket_configs),
+            freeze: FreezeGate::new(),
         }
     }
 
+    /// Share a `FreezeGate` with the `LlmClient` so a 429's `Retry-After`
+    /// pauses this limiter too, instead of only the client.
+    pub fn with_freeze_gate(mut self, freeze: FreezeGate) -> Self {
+        self.freeze = freeze;
+        self
+    }
+
+    /// Check and record a single-cost request against the default model's buckets.
     pub fn check_and_update(&self) -> Result<(), RateLimitError> {
+        self.check_and_update_for(DEFAULT_MODEL, 1)
+    }
+
+    /// Check and record `cost` units (e.g. tokens, not just one request)
+    /// against `model`'s buckets, failing if ANY bucket is exhausted and
+    /// reporting whichever one blocked plus the soonest it'll have room again.
+    pub fn check_and_update_for(&self, model: &str, cost: usize) -> Result<(), RateLimitError> {
+        if let Some(remaining) = self.freeze.remaining() {
+            return Err(RateLimitError::Frozen { remaining });
+        }
+
         let now = Instant::now();
-        let today = Utc::now();
-        
-        // Lock the state for the entire operation
-        let mut state = self.state.lock()
+
+        let mut state = self
+            .state
+            .lock()
             .map_err(|_| RateLimitError::DailyLimit { used: 0, max: 0 })?;
-        
-        // Get current limits
-        let max_per_minute = self.max_per_minute.load(Ordering::Acquire);
-        let max_per_day = self.max_per_day.load(Ordering::Acquire);
-        
-        // Reset daily counter if it's a new day
-        if today.date_naive() != state.last_reset.date_naive() {
-            state.daily_count = 0;
-            state.last_reset = today;
-            state.requests.clear(); // Also clear minute requests
-        }
-        
-        // Remove requests older than 1 minute
-        let one_minute_ago = now - Duration::from_secs(60);
-        state.requests.retain(|&req_time| req_time > one_minute_ago);
-        
-        // Check minute limit
-        if state.requests.len() >= max_per_minute {
-            if let Some(&oldest) = state.requests.front() {
-                let elapsed = now.duration_since(oldest);
-                if elapsed < Duration::from_secs(60) {
-                    let wait_time = Duration::from_secs(60) - elapsed;
-                    return Err(RateLimitError::MinuteLimit { wait_time });
-                }
+
+        let ceilings = state.ceilings.clone();
+        let model_state = state.models.entry(model.to_string()).or_default();
+
+        for config in self.bucket_configs.iter() {
+            let max = ceilings.get(&config.name).copied().unwrap_or(config.max);
+            let window = model_state
+                .buckets
+                .entry(config.name.clone())
+                .or_insert_with(|| BucketWindow::new(max));
+            window.max = max;
+            window.evict(now, config.window);
+
+            if window.used + cost > window.max {
+                let wait_time = window.wait_for_slot(now, config.window).unwrap_or(config.window);
+                return Err(Self::blocked_error(&config.name, wait_time, window.used, window.max));
             }
         }
-        
-        // Check daily limit
-        if state.daily_count >= max_per_day {
-            return Err(RateLimitError::DailyLimit {
-                used: state.daily_count,
-                max: max_per_day,
-            });
-        }
-        
-        // Update counters
-        state.requests.push_back(now);
-        state.daily_count += 1;
-        
-        // Prevent unbounded growth
-        if state.requests.len() > max_per_minute * 2 {
-            state.requests.drain(..state.requests.len() - max_per_minute);
+
+        for config in self.bucket_configs.iter() {
+            let window = model_state
+                .buckets
+                .get_mut(&config.name)
+                .expect("seeded by the admission pass above");
+            window.entries.push_back((now, cost));
+            window.used += cost;
         }
-        
+
         Ok(())
     }
 
-    pub fn remaining_today(&self) -> usize {
-        if let Ok(state) = self.state.lock() {
-            self.max_per_day.load(Ordering::Acquire).saturating_sub(state.daily_count)
-        } else {
-            0
+    /// Known bucket names keep their original dedicated error variants so
+    /// existing callers matching on `MinuteLimit`/`DailyLimit` keep working;
+    /// buckets from a custom `with_buckets` set get the generic variant.
+    fn blocked_error(bucket: &str, wait_time: Duration, used: usize, max: usize) -> RateLimitError {
+        match bucket {
+            "minute" => RateLimitError::MinuteLimit { wait_time },
+            "day" => RateLimitError::DailyLimit { used, max },
+            other => RateLimitError::BucketLimit {
+                bucket: other.to_string(),
+                wait_time,
+            },
         }
     }
 
-    pub fn remaining_this_minute(&self) -> usize {
-        if let Ok(state) = self.state.lock() {
-            self.max_per_minute.load(Ordering::Acquire).saturating_sub(state.requests.len())
+    fn default_bucket_count(&self, bucket: &str) -> usize {
+        let Ok(mut state) = self.state.lock() else {
+            return 0;
+        };
+        let now = Instant::now();
+        let window = self.bucket_configs.iter().find(|c| c.name == bucket).map(|c| c.window);
+        let Some(window) = window else { return 0 };
+
+        let model_state = state.models.entry(DEFAULT_MODEL.to_string()).or_default();
+        if let Some(bucket_state) = model_state.buckets.get_mut(bucket) {
+            bucket_state.evict(now, window);
+            bucket_state.used
         } else {
             0
         }
     }
 
+    pub fn remaining_today(&self) -> usize {
+        let max = self.bucket_max("day");
+        max.saturating_sub(self.default_bucket_count("day"))
+    }
+
+    pub fn remaining_this_minute(&self) -> usize {
+        let max = self.bucket_max("minute");
+        max.saturating_sub(self.default_bucket_count("minute"))
+    }
+
+    fn bucket_max(&self, bucket: &str) -> usize {
+        self.state
+            .lock()
+            .ok()
+            .and_then(|state| state.ceilings.get(bucket).copied())
+            .unwrap_or(0)
+    }
+
     pub fn next_available(&self) -> Option<Duration> {
-        if let Ok(state) = self.state.lock() {
-            let max_per_minute = self.max_per_minute.load(Ordering::Acquire);
-            if state.requests.len() >= max_per_minute {
-                if let Some(&oldest) = state.requests.front() {
-                    let elapsed = Instant::now().duration_since(oldest);
-                    if elapsed < Duration::from_secs(60) {
-                        return Some(Duration::from_secs(60) - elapsed);
-                    }
-                }
-            }
+        if let Some(remaining) = self.freeze.remaining() {
+            return Some(remaining);
         }
-        None
+
+        let mut state = self.state.lock().ok()?;
+        let now = Instant::now();
+        let model_state = state.models.entry(DEFAULT_MODEL.to_string()).or_default();
+
+        self.bucket_configs.iter().find_map(|config| {
+            let bucket_state = model_state.buckets.get_mut(&config.name)?;
+            bucket_state.evict(now, config.window);
+            if bucket_state.used < bucket_state.max {
+                return None;
+            }
+            bucket_state.wait_for_slot(now, config.window)
+        })
     }
 
     pub fn reset_daily_count(&self) {
         if let Ok(mut state) = self.state.lock() {
-            state.daily_count = 0;
             state.last_reset = Utc::now();
-            state.requests.clear();
+            if let Some(model_state) = state.models.get_mut(DEFAULT_MODEL) {
+                model_state.buckets.remove("day");
+                model_state.buckets.remove("minute");
+            }
         }
     }
 
     pub fn get_stats(&self) -> RateLimiterStats {
-        if let Ok(state) = self.state.lock() {
-            RateLimiterStats {
-                requests_this_minute: state.requests.len(),
-                requests_today: state.daily_count,
-                max_per_minute: self.max_per_minute.load(Ordering::Acquire),
-                max_per_day: self.max_per_day.load(Ordering::Acquire),
-                last_reset: state.last_reset,
-            }
-        } else {
-            RateLimiterStats {
-                requests_this_minute: 0,
-                requests_today: 0,
-                max_per_minute: self.max_per_minute.load(Ordering::Acquire),
-                max_per_day: self.max_per_day.load(Ordering::Acquire),
-                last_reset: Utc::now(),
-            }
+        RateLimiterStats {
+            requests_this_minute: self.default_bucket_count("minute"),
+            requests_today: self.default_bucket_count("day"),
+            max_per_minute: self.bucket_max("minute"),
+            max_per_day: self.bucket_max("day"),
+            last_reset: self
+                .state
+                .lock()
+                .map(|state| state.last_reset)
+                .unwrap_or_else(|_| Utc::now()),
         }
     }
-    
+
     pub fn update_limits(&self, max_per_minute: usize, max_per_day: usize) {
-        self.max_per_minute.store(max_per_minute, Ordering::Release);
-        self.max_per_day.store(max_per_day, Ordering::Release);
+        if let Ok(mut state) = self.state.lock() {
+            state.ceilings.insert("minute".to_string(), max_per_minute);
+            state.ceilings.insert("day".to_string(), max_per_day);
+        }
+    }
+
+    /// Reconcile our local window against the live `x-ratelimit-limit-requests`
+    /// / `x-ratelimit-remaining-requests` headers an OpenAI-compatible
+    /// endpoint returns on every response, so our view doesn't drift from the
+    /// account's actual limits over time.
+    pub fn sync_from_headers(&self, headers: &HeaderMap) {
+        let limit = Self::header_usize(headers, "x-ratelimit-limit-requests");
+        let remaining = Self::header_usize(headers, "x-ratelimit-remaining-requests");
+
+        let Ok(mut state) = self.state.lock() else { return };
+
+        if let Some(limit) = limit {
+            state.ceilings.insert("minute".to_string(), limit);
+        }
+
+        let Some(remaining) = remaining else { return };
+
+        let max_per_minute = state.ceilings.get("minute").copied().unwrap_or(0);
+        let now = Instant::now();
+        let model_state = state.models.entry(DEFAULT_MODEL.to_string()).or_default();
+        let bucket = model_state
+            .buckets
+            .entry("minute".to_string())
+            .or_insert_with(|| BucketWindow::new(max_per_minute));
+        bucket.max = max_per_minute;
+
+        let our_remaining = max_per_minute.saturating_sub(bucket.used);
+
+        if remaining < our_remaining {
+            // The server has consumed more of the window than we're aware of
+            // (e.g. another process sharing this key); record the difference
+            // as synthetic requests so our remaining count matches theirs.
+            for _ in 0..(our_remaining - remaining) {
+                bucket.entries.push_back((now, 1));
+                bucket.used += 1;
+            }
+        }
+    }
+
+    fn header_usize(headers: &HeaderMap, name: &str) -> Option<usize> {
+        headers.get(name)?.to_str().ok()?.parse().ok()
     }
 }
 
@@ -180,12 +398,12 @@ mod tests {
     #[test]
     fn test_minute_rate_limit() {
         let limiter = RateLimiter::new(3, 100);
-        
+
         // First 3 requests should succeed
         assert!(limiter.check_and_update().is_ok());
         assert!(limiter.check_and_update().is_ok());
         assert!(limiter.check_and_update().is_ok());
-        
+
         // 4th request should fail
         assert!(matches!(
             limiter.check_and_update(),
@@ -196,12 +414,12 @@ mod tests {
     #[test]
     fn test_daily_rate_limit() {
         let limiter = RateLimiter::new(100, 5);
-        
+
         // Use up daily limit
         for _ in 0..5 {
             assert!(limiter.check_and_update().is_ok());
         }
-        
+
         // Next request should fail with daily limit
         assert!(matches!(
             limiter.check_and_update(),
@@ -212,15 +430,15 @@ mod tests {
     #[test]
     fn test_minute_window_sliding() {
         let limiter = RateLimiter::new(2, 100);
-        
+
         // Use up the limit
         assert!(limiter.check_and_update().is_ok());
         assert!(limiter.check_and_update().is_ok());
         assert!(limiter.check_and_update().is_err());
-        
+
         // Reset for testing
         limiter.reset_daily_count();
-        
+
         // Should work again
         assert!(limiter.check_and_update().is_ok());
     }
@@ -228,12 +446,12 @@ mod tests {
     #[test]
     fn test_remaining_counts() {
         let limiter = RateLimiter::new(5, 10);
-        
+
         assert_eq!(limiter.remaining_today(), 10);
         assert_eq!(limiter.remaining_this_minute(), 5);
-        
+
         limiter.check_and_update().unwrap();
-        
+
         assert_eq!(limiter.remaining_today(), 9);
         assert_eq!(limiter.remaining_this_minute(), 4);
     }
@@ -241,24 +459,126 @@ mod tests {
     #[test]
     fn test_next_available() {
         let limiter = RateLimiter::new(1, 100);
-        
+
         // First request succeeds
         assert!(limiter.check_and_update().is_ok());
-        
+
         // Should have wait time
         let wait = limiter.next_available();
         assert!(wait.is_some());
         assert!(wait.unwrap() <= Duration::from_secs(60));
     }
-    
+
+    #[test]
+    fn test_sync_from_headers_lowers_remaining() {
+        let limiter = RateLimiter::new(10, 100);
+        limiter.check_and_update().unwrap();
+        limiter.check_and_update().unwrap();
+
+        // Server reports only 2 remaining out of 10, vs our computed 8
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert("x-ratelimit-limit-requests", "10".parse().unwrap());
+        headers.insert("x-ratelimit-remaining-requests", "2".parse().unwrap());
+
+        limiter.sync_from_headers(&headers);
+
+        assert_eq!(limiter.remaining_this_minute(), 2);
+    }
+
+    #[test]
+    fn test_sync_from_headers_updates_ceiling() {
+        let limiter = RateLimiter::new(10, 100);
+
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert("x-ratelimit-limit-requests", "60".parse().unwrap());
+        headers.insert("x-ratelimit-remaining-requests", "60".parse().unwrap());
+
+        limiter.sync_from_headers(&headers);
+
+        assert_eq!(limiter.get_stats().max_per_minute, 60);
+    }
+
+    #[test]
+    fn test_freeze_gate_blocks_until_expiry() {
+        let gate = FreezeGate::new();
+        assert!(gate.remaining().is_none());
+
+        gate.freeze_until(Instant::now() + Duration::from_millis(50));
+        assert!(gate.remaining().is_some());
+    }
+
+    #[test]
+    fn test_freeze_gate_does_not_shorten_existing_freeze() {
+        let gate = FreezeGate::new();
+        gate.freeze_until(Instant::now() + Duration::from_secs(60));
+        gate.freeze_until(Instant::now() + Duration::from_millis(1));
+
+        // The shorter freeze should not have shortened the longer one
+        assert!(gate.remaining().unwrap() > Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_check_and_update_respects_freeze() {
+        let limiter = RateLimiter::new(10, 100);
+        let freeze = FreezeGate::new();
+        freeze.freeze_until(Instant::now() + Duration::from_secs(60));
+        let limiter = limiter.with_freeze_gate(freeze);
+
+        assert!(matches!(
+            limiter.check_and_update(),
+            Err(RateLimitError::Frozen { .. })
+        ));
+        assert!(limiter.next_available().is_some());
+    }
+
+    #[test]
+    fn test_with_buckets_reports_which_bucket_blocked() {
+        let limiter = RateLimiter::with_buckets(vec![
+            BucketConfig::new("burst", Duration::from_secs(1), 2),
+            BucketConfig::new("sustained", Duration::from_secs(60), 100),
+        ]);
+
+        assert!(limiter.check_and_update_for("gpt-4", 1).is_ok());
+        assert!(limiter.check_and_update_for("gpt-4", 1).is_ok());
+
+        match limiter.check_and_update_for("gpt-4", 1) {
+            Err(RateLimitError::BucketLimit { bucket, .. }) => assert_eq!(bucket, "burst"),
+            other => panic!("expected BucketLimit for 'burst', got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_with_buckets_enforces_token_cost() {
+        let limiter = RateLimiter::with_buckets(vec![BucketConfig::new(
+            "tokens",
+            Duration::from_secs(60),
+            1000,
+        )]);
+
+        assert!(limiter.check_and_update_for("gpt-4", 600).is_ok());
+        assert!(limiter.check_and_update_for("gpt-4", 500).is_err());
+        assert!(limiter.check_and_update_for("gpt-4", 400).is_ok());
+    }
+
+    #[test]
+    fn test_with_buckets_keys_independent_limits_per_model() {
+        let limiter =
+            RateLimiter::with_buckets(vec![BucketConfig::new("minute", Duration::from_secs(60), 1)]);
+
+        assert!(limiter.check_and_update_for("gpt-4", 1).is_ok());
+        // A different model has its own independent bucket, unaffected by gpt-4's usage.
+        assert!(limiter.check_and_update_for("claude-3", 1).is_ok());
+        assert!(limiter.check_and_update_for("gpt-4", 1).is_err());
+    }
+
     #[test]
     fn test_thread_safety() {
         use std::sync::Arc;
         use std::thread;
-        
+
         let limiter = Arc::new(RateLimiter::new(10, 100));
         let mut handles = vec![];
-        
+
         // Spawn multiple threads trying to use the rate limiter
         for _ in 0..5 {
             let limiter_clone = Arc::clone(&limiter);
@@ -270,14 +590,14 @@ mod tests {
             });
             handles.push(handle);
         }
-        
+
         // Wait for all threads
         for handle in handles {
             handle.join().unwrap();
         }
-        
+
         // Check that we didn't exceed limits
         let stats = limiter.get_stats();
         assert!(stats.requests_today <= 100);
     }
-}
\ No newline at end of file
+}
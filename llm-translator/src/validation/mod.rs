@@ -1,5 +1,5 @@
 pub mod text;
 pub mod limits;
 
-pub use text::{TextValidator, TextValidationResult, TextValidationError};
-pub use limits::{RateLimiter, RateLimitError};
\ No newline at end of file
+pub use text::{TextValidator, TextValidationResult, TextValidationError, ModelKind, TextChunk};
+pub use limits::{RateLimiter, RateLimitError, FreezeGate, BucketConfig};
\ No newline at end of file
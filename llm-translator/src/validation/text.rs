@@ -1,4 +1,56 @@
+use std::sync::Arc;
+
 use thiserror::Error;
+use tiktoken_rs::{cl100k_base, o200k_base, CoreBPE};
+
+/// Which BPE merge table to tokenize against, chosen by target model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModelKind {
+    /// GPT-3.5 / GPT-4 family - `cl100k_base`.
+    Gpt4,
+    /// GPT-4o family - `o200k_base`.
+    Gpt4o,
+    /// Unrecognized model - falls back to `cl100k_base` as the closest
+    /// general-purpose approximation.
+    Other,
+}
+
+impl ModelKind {
+    /// Guess the encoding family from a model name like `"gpt-4o-mini"` or
+    /// `"gpt-3.5-turbo"`.
+    pub fn from_model_name(model: &str) -> Self {
+        let model = model.to_lowercase();
+        if model.starts_with("gpt-4o") || model.starts_with("o1") || model.starts_with("o3") {
+            ModelKind::Gpt4o
+        } else if model.starts_with("gpt-4") || model.starts_with("gpt-3.5") || model.starts_with("gpt-35") {
+            ModelKind::Gpt4
+        } else {
+            ModelKind::Other
+        }
+    }
+
+    fn load_bpe(self) -> Option<CoreBPE> {
+        match self {
+            ModelKind::Gpt4o => o200k_base().ok(),
+            ModelKind::Gpt4 | ModelKind::Other => cl100k_base().ok(),
+        }
+    }
+}
+
+impl Default for ModelKind {
+    fn default() -> Self {
+        ModelKind::Other
+    }
+}
+
+/// One piece of a long text produced by [`TextValidator::split_text`], sized
+/// to fit within `max_tokens_estimate`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextChunk {
+    pub text: String,
+    pub index: usize,
+    pub token_count: usize,
+}
 
 #[derive(Debug, Clone)]
 pub struct TextValidator {
@@ -8,6 +60,17 @@ pub struct TextValidator {
     allow_only_whitespace: bool,
     detect_binary_data: bool,
     trim_before_validate: bool,
+    model: ModelKind,
+    tokenizer: Option<Arc<CoreBPE>>,
+    chunk_overlap_tokens: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TruncateDirection {
+    /// Cut off the start of the text, keeping its tail.
+    Start,
+    /// Cut off the end of the text, keeping its head.
+    End,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -25,19 +88,19 @@ pub enum TextValidationResult {
 pub enum TextValidationError {
     #[error("Text is empty")]
     Empty,
-    
+
     #[error("Text is too short: {length} characters, minimum: {min}")]
     TooShort { length: usize, min: usize },
-    
+
     #[error("Text is too long: {length} characters, maximum: {max}")]
     TooLong { length: usize, max: usize },
-    
+
     #[error("Text has too many tokens: ~{estimated}, maximum: {max}")]
     TooManyTokens { estimated: usize, max: usize },
-    
+
     #[error("Text contains only whitespace")]
     OnlyWhitespace,
-    
+
     #[error("Text contains binary data")]
     ContainsBinary,
 }
@@ -48,6 +111,7 @@ impl TextValidator {
         max_tokens_estimate: usize,
         min_length: usize,
     ) -> Self {
+        let model = ModelKind::default();
         Self {
             max_length,
             max_tokens_estimate,
@@ -55,6 +119,11 @@ impl TextValidator {
             allow_only_whitespace: false,
             detect_binary_data: true,
             trim_before_validate: true,
+            model,
+            // Best-effort: the BPE merge tables may not be reachable offline,
+            // in which case callers fall back to the char/4 heuristic below.
+            tokenizer: model.load_bpe().map(Arc::new),
+            chunk_overlap_tokens: 0,
         }
     }
 
@@ -73,6 +142,65 @@ impl TextValidator {
         self
     }
 
+    /// Select the tokenizer by target model family, e.g.
+    /// `ModelKind::from_model_name(&config.api.model)`.
+    pub fn with_tokenizer(mut self, model: ModelKind) -> Self {
+        self.model = model;
+        self.tokenizer = model.load_bpe().map(Arc::new);
+        self
+    }
+
+    /// Carry the last `tokens` tokens of each chunk onto the front of the
+    /// next one emitted by `split_text`, so translations stay coherent
+    /// across chunk boundaries. Zero (the default) disables overlap.
+    pub fn with_overlap(mut self, tokens: usize) -> Self {
+        self.chunk_overlap_tokens = tokens;
+        self
+    }
+
+    /// Count tokens with the real BPE tokenizer when available, falling back
+    /// to a rough 4-characters-per-token estimate otherwise.
+    pub fn count_tokens(&self, text: &str) -> usize {
+        match &self.tokenizer {
+            Some(bpe) => bpe.encode_ordinary(text).len(),
+            None => (text.chars().count() + 3) / 4,
+        }
+    }
+
+    /// Truncate `text` to at most `max_tokens` tokens by cutting off the
+    /// `Start` or `End` of the encoded sequence (see [`TruncateDirection`]),
+    /// and decode back to a valid UTF-8 string so a multi-byte character is
+    /// never split.
+    pub fn truncate(&self, text: &str, max_tokens: usize, direction: TruncateDirection) -> String {
+        match &self.tokenizer {
+            Some(bpe) => {
+                let tokens = bpe.encode_ordinary(text);
+                if tokens.len() <= max_tokens {
+                    return text.to_string();
+                }
+
+                let kept = match direction {
+                    TruncateDirection::Start => &tokens[tokens.len() - max_tokens..],
+                    TruncateDirection::End => &tokens[..max_tokens],
+                };
+
+                bpe.decode(kept.to_vec()).unwrap_or_default()
+            }
+            None => {
+                let max_chars = max_tokens * 4;
+                let chars: Vec<char> = text.chars().collect();
+                if chars.len() <= max_chars {
+                    return text.to_string();
+                }
+
+                match direction {
+                    TruncateDirection::Start => chars[chars.len() - max_chars..].iter().collect(),
+                    TruncateDirection::End => chars[..max_chars].iter().collect(),
+                }
+            }
+        }
+    }
+
     pub fn validate(&self, text: &str) -> Result<String, TextValidationError> {
         let processed = if self.trim_before_validate {
             text.trim()
@@ -106,8 +234,8 @@ impl TextValidator {
             });
         }
 
-        // Estimate tokens (roughly 4 chars = 1 token for most languages)
-        let estimated_tokens = (char_count + 3) / 4; // Round up
+        // Count real tokens against the target model's tokenizer
+        let estimated_tokens = self.count_tokens(processed);
         if estimated_tokens > self.max_tokens_estimate {
             return Err(TextValidationError::TooManyTokens {
                 estimated: estimated_tokens,
@@ -120,7 +248,7 @@ impl TextValidator {
             let has_binary = processed.chars().any(|c| {
                 c.is_control() && c != '\n' && c != '\r' && c != '\t'
             });
-            
+
             if has_binary {
                 return Err(TextValidationError::ContainsBinary);
             }
@@ -129,96 +257,165 @@ impl TextValidator {
         Ok(processed.to_string())
     }
 
-    pub fn split_text(&self, text: &str) -> Vec<String> {
+    /// Split `text` into token-budgeted chunks for piecewise translation.
+    ///
+    /// Pieces are carved out by recursing through separators in priority
+    /// order - paragraphs, then lines, then sentences, then words, then a
+    /// hard token/char split as a last resort - so a finer boundary is only
+    /// used where a coarser one leaves a piece over budget. The pieces are then greedily packed
+    /// into chunks up to `max_tokens_estimate`, and if `with_overlap` was
+    /// used, each chunk after the first is seeded with the tail of the
+    /// previous one so context carries across the boundary.
+    pub fn split_text(&self, text: &str) -> Vec<TextChunk> {
         let processed = if self.trim_before_validate {
             text.trim()
         } else {
             text
         };
 
-        if processed.chars().count() <= self.max_length {
-            return vec![processed.to_string()];
+        if processed.is_empty() {
+            return Vec::new();
         }
 
-        // Smart splitting by sentences or paragraphs
-        let mut chunks = Vec::new();
-        let mut current_chunk = String::new();
-        
-        // Try to split by paragraphs first
-        let paragraphs: Vec<&str> = processed.split("\n\n").collect();
-        
-        for paragraph in paragraphs {
-            let chunk_chars = current_chunk.chars().count();
-            let para_chars = paragraph.chars().count();
-            if chunk_chars + para_chars + 2 <= self.max_length {
-                if !current_chunk.is_empty() {
-                    current_chunk.push_str("\n\n");
-                }
-                current_chunk.push_str(paragraph);
-            } else {
-                if !current_chunk.is_empty() {
-                    chunks.push(current_chunk.clone());
-                    current_chunk.clear();
-                }
-                
-                // If paragraph itself is too long, split by sentences
-                if paragraph.chars().count() > self.max_length {
-                    let sentences = self.split_by_sentences(paragraph);
-                    for sentence in sentences {
-                        chunks.push(sentence);
-                    }
-                } else {
-                    current_chunk = paragraph.to_string();
-                }
-            }
+        let token_count = self.count_tokens(processed);
+        if token_count <= self.max_tokens_estimate {
+            return vec![TextChunk {
+                text: processed.to_string(),
+                index: 0,
+                token_count,
+            }];
         }
-        
-        if !current_chunk.is_empty() {
-            chunks.push(current_chunk);
+
+        let pieces = self.split_into_pieces(processed, 0);
+        self.pack_chunks(pieces)
+    }
+
+    /// Recursively carve `text` into pieces that each fit `max_tokens_estimate`,
+    /// trying separators in priority order before falling back to a hard
+    /// split. `level` indexes into the separator priority list below; no
+    /// characters are dropped since `split_inclusive` keeps the separator
+    /// attached to the piece that precedes it.
+    fn split_into_pieces(&self, text: &str, level: usize) -> Vec<String> {
+        if text.is_empty() {
+            return Vec::new();
+        }
+
+        if self.count_tokens(text) <= self.max_tokens_estimate {
+            return vec![text.to_string()];
         }
-        
-        chunks
-    }
-
-    fn split_by_sentences(&self, text: &str) -> Vec<String> {
-        let mut chunks = Vec::new();
-        let mut current = String::new();
-        
-        // Simple sentence splitting (can be improved with proper NLP)
-        let sentences = text.split_inclusive(|c| c == '.' || c == '!' || c == '?');
-        
-        for sentence in sentences {
-            let current_chars = current.chars().count();
-            let sentence_chars = sentence.chars().count();
-            if current_chars + sentence_chars <= self.max_length {
-                current.push_str(sentence);
+
+        let segments: Vec<&str> = match level {
+            0 => text.split_inclusive("\n\n").collect(),
+            1 => text.split_inclusive('\n').collect(),
+            2 => text.split_inclusive(|c: char| c == '.' || c == '!' || c == '?').collect(),
+            3 => text.split_inclusive(' ').collect(),
+            // No separator left that could make progress - hard split on
+            // token (or char, without a tokenizer) boundaries. This always
+            // terminates since hard_split bounds every piece by the budget.
+            _ => return self.hard_split(text),
+        };
+
+        if segments.len() <= 1 {
+            // This separator isn't present in `text` - try the next one.
+            return self.split_into_pieces(text, level + 1);
+        }
+
+        segments
+            .into_iter()
+            .flat_map(|segment| self.split_into_pieces(segment, level + 1))
+            .collect()
+    }
+
+    /// Greedily pack pieces into chunks bounded by `max_tokens_estimate`,
+    /// prepending `chunk_overlap_tokens` worth of the previous chunk onto
+    /// each subsequent one.
+    fn pack_chunks(&self, pieces: Vec<String>) -> Vec<TextChunk> {
+        let mut result = Vec::new();
+        let mut overlap_prefix = String::new();
+        let mut core = String::new();
+
+        for piece in pieces {
+            let piece_tokens = self.count_tokens(&piece);
+            let budget_used = self.count_tokens(&overlap_prefix) + self.count_tokens(&core);
+
+            if !core.is_empty() && budget_used + piece_tokens > self.max_tokens_estimate {
+                let text = format!("{}{}", overlap_prefix, core);
+                let token_count = self.count_tokens(&text);
+                let next_overlap = self.overlap_suffix(&core);
+                result.push(TextChunk {
+                    text,
+                    index: result.len(),
+                    token_count,
+                });
+                core = piece;
+                // `core` just became the piece that didn't fit, which can
+                // itself already be up to `max_tokens_estimate` tokens (see
+                // `hard_split`) - shrink the carried-over overlap so it
+                // still leaves room, rather than letting the next emitted
+                // chunk's overlap_prefix + core run past the budget.
+                overlap_prefix = self.clamp_overlap(next_overlap, piece_tokens);
             } else {
-                if !current.is_empty() {
-                    chunks.push(current.clone());
-                    current.clear();
-                }
-                
-                // If sentence is still too long, hard split
-                if sentence.chars().count() > self.max_length {
-                    chunks.extend(self.hard_split(sentence));
-                } else {
-                    current = sentence.to_string();
-                }
+                core.push_str(&piece);
             }
         }
-        
-        if !current.is_empty() {
-            chunks.push(current);
+
+        if !core.is_empty() {
+            let text = format!("{}{}", overlap_prefix, core);
+            let token_count = self.count_tokens(&text);
+            result.push(TextChunk {
+                text,
+                index: result.len(),
+                token_count,
+            });
+        }
+
+        result
+    }
+
+    /// The last `chunk_overlap_tokens` tokens of `text`, clamped so the
+    /// overlap itself never exceeds the chunk budget.
+    fn overlap_suffix(&self, text: &str) -> String {
+        let overlap = self
+            .chunk_overlap_tokens
+            .min(self.max_tokens_estimate.saturating_sub(1));
+        if overlap == 0 {
+            return String::new();
+        }
+        self.truncate(text, overlap, TruncateDirection::Start)
+    }
+
+    /// Shrink `overlap_prefix` so it and `core_tokens` together fit
+    /// `max_tokens_estimate`, keeping the end of `overlap_prefix` (the part
+    /// closest to where `core` begins) and dropping the rest - down to
+    /// nothing if `core` alone already fills the budget.
+    fn clamp_overlap(&self, overlap_prefix: String, core_tokens: usize) -> String {
+        if core_tokens >= self.max_tokens_estimate {
+            return String::new();
         }
-        
-        chunks
+        let remaining = self.max_tokens_estimate - core_tokens;
+        if self.count_tokens(&overlap_prefix) <= remaining {
+            return overlap_prefix;
+        }
+        self.truncate(&overlap_prefix, remaining, TruncateDirection::Start)
     }
 
     fn hard_split(&self, text: &str) -> Vec<String> {
-        let chars: Vec<char> = text.chars().collect();
-        chars.chunks(self.max_length)
-            .map(|chunk| chunk.iter().collect::<String>())
-            .collect()
+        let budget = self.max_tokens_estimate.max(1);
+
+        match &self.tokenizer {
+            Some(bpe) => bpe
+                .encode_ordinary(text)
+                .chunks(budget)
+                .map(|chunk| bpe.decode(chunk.to_vec()).unwrap_or_default())
+                .collect(),
+            None => {
+                let chars: Vec<char> = text.chars().collect();
+                chars
+                    .chunks(budget * 4)
+                    .map(|chunk| chunk.iter().collect::<String>())
+                    .collect()
+            }
+        }
     }
 }
 
@@ -281,60 +478,187 @@ mod tests {
         assert_eq!(result.unwrap(), "Hello, world!");
     }
 
+    #[test]
+    fn test_model_kind_from_model_name() {
+        assert_eq!(ModelKind::from_model_name("gpt-4o-mini"), ModelKind::Gpt4o);
+        assert_eq!(ModelKind::from_model_name("gpt-4-turbo"), ModelKind::Gpt4);
+        assert_eq!(ModelKind::from_model_name("gpt-3.5-turbo"), ModelKind::Gpt4);
+        assert_eq!(ModelKind::from_model_name("claude-3-opus"), ModelKind::Other);
+    }
+
+    #[test]
+    fn test_with_tokenizer_switches_encoding() {
+        let validator = TextValidator::new(1000, 250, 1).with_tokenizer(ModelKind::Gpt4o);
+        // Just exercising the o200k_base path end to end; exact counts are
+        // an implementation detail of the merge table.
+        assert!(validator.count_tokens("Hello, world!") > 0);
+    }
+
+    #[test]
+    fn test_too_many_tokens_reports_real_count() {
+        let validator = TextValidator::new(10_000, 3, 1);
+        let text = "one two three four five six seven eight nine ten";
+        match validator.validate(text) {
+            Err(TextValidationError::TooManyTokens { estimated, max }) => {
+                assert_eq!(max, 3);
+                assert_eq!(estimated, validator.count_tokens(text));
+            }
+            other => panic!("expected TooManyTokens, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_truncate_keeps_within_budget() {
+        let validator = TextValidator::new(1000, 250, 1);
+        let text = "The quick brown fox jumps over the lazy dog repeatedly and again and again";
+
+        let truncated_end = validator.truncate(text, 5, TruncateDirection::End);
+        assert!(validator.count_tokens(&truncated_end) <= 5);
+
+        let truncated_start = validator.truncate(text, 5, TruncateDirection::Start);
+        assert!(validator.count_tokens(&truncated_start) <= 5);
+        assert_ne!(truncated_start, truncated_end);
+    }
+
+    #[test]
+    fn test_truncate_is_noop_under_budget() {
+        let validator = TextValidator::new(1000, 250, 1);
+        let text = "short text";
+        assert_eq!(validator.truncate(text, 100, TruncateDirection::End), text);
+    }
+
     #[test]
     fn test_text_splitting() {
-        let validator = TextValidator::new(50, 20, 1);
+        let validator = TextValidator::new(1000, 20, 1);
         let long_text = "This is a long text. It has multiple sentences. Each sentence should be split properly.";
         let chunks = validator.split_text(long_text);
-        
+
+        assert!(chunks.len() > 1);
+        for (i, chunk) in chunks.iter().enumerate() {
+            assert_eq!(chunk.index, i);
+            assert_eq!(chunk.token_count, validator.count_tokens(&chunk.text));
+            assert!(chunk.token_count <= 20);
+        }
+    }
+
+    #[test]
+    fn test_split_text_empty_is_empty() {
+        let validator = TextValidator::new(1000, 20, 1);
+        assert!(validator.split_text("").is_empty());
+    }
+
+    #[test]
+    fn test_split_text_under_budget_is_single_chunk() {
+        let validator = TextValidator::new(1000, 20, 1);
+        let chunks = validator.split_text("short text");
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0].index, 0);
+        assert_eq!(chunks[0].text, "short text");
+    }
+
+    #[test]
+    fn test_split_text_indivisible_run_still_emits() {
+        // A single long run with no separators at all must still be chunked
+        // via the hard-split fallback rather than recursing forever.
+        let validator = TextValidator::new(10_000, 5, 1);
+        let long_word = "a".repeat(200);
+        let chunks = validator.split_text(&long_word);
+
+        assert!(chunks.len() > 1);
+        for chunk in &chunks {
+            assert!(chunk.token_count <= 5);
+        }
+    }
+
+    #[test]
+    fn test_split_text_overlap_carries_tail_into_next_chunk() {
+        let validator = TextValidator::new(10_000, 8, 1).with_overlap(3);
+        let long_text = "one two three four five six seven eight nine ten eleven twelve thirteen fourteen";
+        let chunks = validator.split_text(long_text);
+
+        assert!(chunks.len() > 1);
+        // Every chunk after the first should start with the tail of the one before it.
+        for pair in chunks.windows(2) {
+            let expected_overlap = validator.overlap_suffix(&pair[0].text);
+            assert!(pair[1].text.starts_with(&expected_overlap));
+        }
+    }
+
+    #[test]
+    fn test_split_text_overlap_never_exceeds_budget() {
+        // Overlap larger than the budget must be clamped, not break chunking,
+        // and every emitted chunk must still respect max_tokens_estimate.
+        let validator = TextValidator::new(10_000, 5, 1).with_overlap(1000);
+        let long_text = "one two three four five six seven eight nine ten";
+        let chunks = validator.split_text(long_text);
         assert!(chunks.len() > 1);
         for chunk in &chunks {
-            assert!(chunk.len() <= 50);
+            assert!(chunk.token_count <= 5, "chunk exceeded budget: {} tokens", chunk.token_count);
+        }
+
+        // A core near the full budget on its own (guaranteed by hard_split,
+        // since there's no separator to pack around) combined with an
+        // overlap clamped near the budget must still never double it.
+        let no_separators = "a".repeat(200);
+        let hard_split_chunks = validator.split_text(&no_separators);
+        assert!(hard_split_chunks.len() > 1);
+        for chunk in &hard_split_chunks {
+            assert!(chunk.token_count <= 5, "chunk exceeded budget: {} tokens", chunk.token_count);
         }
     }
 
+    #[test]
+    fn test_split_text_zero_overlap_drops_no_characters() {
+        let validator = TextValidator::new(10_000, 20, 1);
+        let long_text = "This is a long text. It has multiple sentences. Each sentence should be split properly.";
+        let chunks = validator.split_text(long_text);
+
+        let combined: String = chunks.iter().map(|c| c.text.as_str()).collect();
+        assert_eq!(combined, long_text);
+    }
+
     #[test]
     fn test_unicode_handling() {
         let validator = TextValidator::new(1000, 250, 1);
-        let unicode_text = "ÐŸÑ€Ð¸Ð²ÐµÑ‚, Ð¼Ð¸Ñ€! ä½ å¥½ä¸–ç•Œ ðŸŒ";
+        let unicode_text = "ÐŸÑ€Ð¸Ð²ÐµÑ‚, Ð¼Ð¸Ñ€! ä½ å¥½ä¸–ç•Œ ðŸŒ";
         let result = validator.validate(unicode_text);
         assert!(result.is_ok());
     }
-    
+
     #[test]
     fn test_unicode_length_counting() {
-        let validator = TextValidator::new(10, 10, 1);
-        
+        let validator = TextValidator::new(10, 250, 1);
+
         // This string has 10 characters but many more bytes
-        let unicode_text = "ä½ å¥½ä¸–ç•ŒðŸŒðŸŒðŸŒŽâ¤ï¸ðŸ‘¨â€ðŸ‘©â€ðŸ‘§â€ðŸ‘¦";
+        let unicode_text = "ä½ å¥½ä¸–ç•ŒðŸŒðŸŒðŸŒŽâ¤ï¸ðŸ‘¨â€ðŸ‘©â€ðŸ‘§â€ðŸ‘¦";
         let result = validator.validate(unicode_text);
-        
+
         // Should pass because it's exactly 10 characters
         assert!(result.is_ok());
-        
+
         // Now test with 11 characters
-        let too_long = "ä½ å¥½ä¸–ç•ŒðŸŒðŸŒðŸŒŽâ¤ï¸ðŸ‘¨â€ðŸ‘©â€ðŸ‘§â€ðŸ‘¦!";
+        let too_long = "ä½ å¥½ä¸–ç•ŒðŸŒðŸŒðŸŒŽâ¤ï¸ðŸ‘¨â€ðŸ‘©â€ðŸ‘§â€ðŸ‘¦!";
         let result = validator.validate(too_long);
-        
+
         // Should fail as too long
         assert!(matches!(result, Err(TextValidationError::TooLong { .. })));
     }
-    
+
     #[test]
     fn test_unicode_splitting() {
-        let validator = TextValidator::new(5, 5, 1);
-        
+        let validator = TextValidator::new(1000, 5, 1);
+
         // Test splitting with Unicode characters
-        let text = "ä½ å¥½ä¸–ç•ŒðŸŒ Hello!";
+        let text = "ä½ å¥½ä¸–ç•ŒðŸŒ Hello!";
         let chunks = validator.split_text(text);
-        
-        // Each chunk should have at most 5 characters
+
+        // Each chunk should fit the token budget
         for chunk in &chunks {
-            assert!(chunk.chars().count() <= 5);
+            assert!(chunk.token_count <= 5);
         }
-        
+
         // Ensure no characters were lost
-        let combined: String = chunks.join("");
+        let combined: String = chunks.iter().map(|c| c.text.as_str()).collect();
         assert_eq!(combined.chars().count(), text.chars().count());
     }
-}
\ No newline at end of file
+}